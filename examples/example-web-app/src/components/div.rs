@@ -1,7 +1,9 @@
-use nestix::{Element, Fragment, Layout, closure, component, effect, layout, props};
+use nestix::{Element, Fragment, Layout, component, effect, layout, props};
 use wasm_bindgen::JsCast;
 use web_sys::HtmlElement;
 
+use crate::renderer::{DomNode, DomRenderer};
+
 #[props(debug)]
 #[derive(Debug)]
 pub struct DivProps {
@@ -12,10 +14,8 @@ pub struct DivProps {
 
 #[component]
 pub fn Div(props: &DivProps, element: &Element) -> Element {
-    let document = web_sys::window().unwrap().document().unwrap();
-    let html_element = document
-        .create_element("div")
-        .unwrap()
+    let html_element = element
+        .create_host_node::<DomRenderer>(DomNode::Element("div"))
         .dyn_into::<HtmlElement>()
         .unwrap();
 
@@ -29,30 +29,6 @@ pub fn Div(props: &DivProps, element: &Element) -> Element {
         }
     );
 
-    element.on_place(closure!(
-        [html_element] | placement | {
-            if let Some(pred) = &placement.pred {
-                if let Some(handle) = pred.downcast_ref::<HtmlElement>() {
-                    handle.after_with_node_1(&html_element).unwrap();
-                } else if let Some(handle) = pred.downcast_ref::<web_sys::Text>() {
-                    handle.after_with_node_1(&html_element).unwrap();
-                }
-            } else if let Some(parent) = &placement.parent {
-                if let Some(parent) = parent.downcast_ref::<HtmlElement>() {
-                    parent.append_child(&html_element).unwrap();
-                }
-            }
-        }
-    ));
-
-    element.on_unmount(closure!(
-        [html_element] || {
-            html_element.remove();
-        }
-    ));
-
-    element.provide_handle(html_element.clone());
-
     layout! {
         Fragment {
             $(props.children.clone())
@@ -1,25 +1,8 @@
-use std::{cell::RefCell, collections::HashMap};
-
-use nanoid_wasm::nanoid;
-use nestix::{Element, Shared, closure, component, effect, props};
+use nestix::{Element, Shared, State, closure, component, effect, on_cleanup, props};
 use wasm_bindgen::{JsCast, prelude::Closure};
 use web_sys::{Event, HtmlElement, HtmlInputElement};
 
-thread_local! {
-    static HANDLERS: RefCell<HashMap<String, InputEventHandlers>> = RefCell::new(HashMap::new());
-}
-
-struct InputEventHandlers {
-    on_value_change: Option<Closure<dyn Fn(Event)>>,
-}
-
-impl InputEventHandlers {
-    fn new() -> Self {
-        Self {
-            on_value_change: None,
-        }
-    }
-}
+use crate::renderer::{DomNode, DomRenderer};
 
 #[props(debug)]
 #[derive(Debug)]
@@ -27,89 +10,64 @@ pub struct InputProps {
     #[props(default)]
     value: String,
     on_value_change: Option<Shared<dyn Fn(String)>>,
+    /// Two-way binding that is used instead of `value` when present.
+    ///
+    /// A [`Lens`](nestix::Lens) onto a field of a larger state converts into a
+    /// `State` with `.into()`, so the input can edit that field directly.
+    #[props(default)]
+    bind: Option<State<String>>,
 }
 
 #[component]
 pub fn Input(props: &InputProps, element: &Element) {
-    let document = web_sys::window().unwrap().document().unwrap();
-    let html_element = document
-        .create_element("input")
-        .unwrap()
+    let html_element = element
+        .create_host_node::<DomRenderer>(DomNode::Element("input"))
         .dyn_into::<HtmlElement>()
         .unwrap();
 
-    let input_id = nanoid!();
-    HANDLERS.with_borrow_mut(|handlers| {
-        handlers.insert(input_id.clone(), InputEventHandlers::new());
-    });
-
-    element.on_place(closure!(
-        [html_element] | placement | {
-            if let Some(pred) = &placement.pred {
-                if let Some(handle) = pred.downcast_ref::<HtmlElement>() {
-                    handle.after_with_node_1(&html_element).unwrap();
-                } else if let Some(handle) = pred.downcast_ref::<web_sys::Text>() {
-                    handle.after_with_node_1(&html_element).unwrap();
+    effect!(
+        [html_element, props.on_value_change, props.bind] || {
+            let on_value_change = on_value_change.get();
+            let bind = bind.get();
+            if on_value_change.is_none() && bind.is_none() {
+                return;
+            }
+            let cb = Closure::wrap(Box::new(closure!([on_value_change, bind] |event: Event| {
+                let value = event
+                    .target()
+                    .unwrap()
+                    .dyn_ref::<HtmlInputElement>()
+                    .unwrap()
+                    .value();
+                if let Some(bind) = &bind {
+                    bind.set(value.clone());
                 }
-            } else if let Some(parent) = &placement.parent {
-                if let Some(parent) = parent.downcast_ref::<HtmlElement>() {
-                    parent.append_child(&html_element).unwrap();
+                if let Some(on_value_change) = &on_value_change {
+                    on_value_change(value);
                 }
-            }
-        }
-    ));
-
-    effect!(
-        [html_element, input_id, props.on_value_change]
-            || {
-                let cb = if let Some(on_value_change) = on_value_change.get() {
-                    Some(Closure::wrap(
-                        Box::new(closure!([on_value_change] |event: Event| {
-                            let value = event.target().unwrap().dyn_ref::<HtmlInputElement>().unwrap().value();
-                            on_value_change(value);
-                        })) as Box<dyn Fn(_)>,
-                    ))
-                } else {
-                    None
-                };
-
-                HANDLERS.with_borrow_mut(|handlers| {
-                    let handlers = handlers.get_mut(&input_id).unwrap();
-                    if let Some(cb) = handlers.on_value_change.take() {
-                        html_element
-                            .remove_event_listener_with_callback(
-                                "input",
-                                cb.as_ref().unchecked_ref(),
-                            )
-                            .unwrap();
-                    }
-                });
+            })) as Box<dyn Fn(_)>);
 
-                if let Some(cb) = cb {
-                    html_element
-                        .add_event_listener_with_callback("input", cb.as_ref().unchecked_ref())
-                        .unwrap();
+            html_element
+                .add_event_listener_with_callback("input", cb.as_ref().unchecked_ref())
+                .unwrap();
 
-                    HANDLERS.with_borrow_mut(|handlers| {
-                        let handlers = handlers.get_mut(&input_id).unwrap();
-                        handlers.on_value_change.replace(cb);
-                    });
-                }
-            }
+            let html_element = html_element.clone();
+            on_cleanup(move || {
+                html_element
+                    .remove_event_listener_with_callback("input", cb.as_ref().unchecked_ref())
+                    .unwrap();
+            });
+        }
     );
 
     effect!(
-        [html_element, props.value] || {
+        [html_element, props.value, props.bind] || {
             let html_input_element = html_element.dyn_ref::<HtmlInputElement>().unwrap();
-            html_input_element.set_value(&value.get());
+            let value = match bind.get() {
+                Some(bind) => bind.get(),
+                None => value.get(),
+            };
+            html_input_element.set_value(&value);
         }
     );
-
-    element.on_unmount(closure!(
-        [html_element] || {
-            html_element.remove();
-        }
-    ));
-
-    element.provide_handle(html_element.clone());
 }
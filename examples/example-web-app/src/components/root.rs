@@ -1,6 +1,5 @@
 use nestix::{Element, Fragment, Layout, component, layout, props};
-use wasm_bindgen::JsCast;
-use web_sys::HtmlElement;
+use web_sys::Node;
 
 #[props(debug)]
 #[derive(Debug)]
@@ -13,14 +12,9 @@ pub struct RootProps {
 pub fn Root(props: &RootProps, element: &Element) -> Element {
     let document = web_sys::window().unwrap().document().unwrap();
     let body = document.body().expect("document should have a body");
-    let html_element = body
-        .query_selector("#root")
-        .unwrap()
-        .unwrap()
-        .dyn_into::<HtmlElement>()
-        .unwrap();
+    let root_node: Node = body.query_selector("#root").unwrap().unwrap().into();
 
-    element.provide_handle(html_element);
+    element.provide_handle(root_node);
 
     layout! {
         Fragment {
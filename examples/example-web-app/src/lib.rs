@@ -1,21 +1,22 @@
 mod components;
+mod renderer;
 
 use std::mem;
 
 use components::*;
-use indexmap::IndexMap;
 use nanoid_wasm::nanoid;
 use nestix::{
-    Element, Shared, callback, component, computed, create_state, destructure, layout, mount_root,
-    props,
+    Element, Shared, State, callback, component, computed, create_state, create_state_map,
+    destructure, layout, mount_root_with_renderer, props,
 };
+use renderer::DomRenderer;
 use wasm_bindgen::prelude::wasm_bindgen;
 
 #[wasm_bindgen(start)]
 fn init() {
     wasm_logger::init(wasm_logger::Config::default());
     let app = layout! { App };
-    mount_root(&app);
+    mount_root_with_renderer(&app, DomRenderer);
     mem::forget(app);
 }
 
@@ -91,59 +92,56 @@ fn Counter() -> Element {
     }
 }
 
+/// The todo being written in the input above the list.
+#[derive(Debug, Clone, Default, PartialEq)]
+struct TodoDraft {
+    content: String,
+}
+
 #[component]
 fn TodoList() -> Element {
-    let items = create_state::<IndexMap<String, String>>(IndexMap::new());
-    let input_value = create_state(String::new());
+    let items = create_state_map::<String, String>([]);
+    let draft = create_state(TodoDraft::default());
+    let content: State<String> = draft
+        .lens(
+            |draft| draft.content.clone(),
+            |draft, content| draft.content = content,
+        )
+        .into();
 
     let add = callback!(
-        [input_value, items] || {
-            items.mutate(|items| {
-                items.insert(nanoid!(), input_value.get());
-            });
-            input_value.set(String::new());
+        [draft, items] || {
+            items.insert(nanoid!(), draft.get().content);
+            draft.set(TodoDraft::default());
         }
     );
 
     let remove = callback!([items] |key: &str| {
-        items.mutate(|items| {
-            items.shift_remove(key);
-        });
+        items.remove(&key.to_string());
     });
 
     let move_up = callback!([items] |key: &str| {
-        items.mutate(|items| {
-            if let Some(index) = items.get_index_of(key) {
-                if index > 0 {
-                    items.swap_indices(index, index - 1);
-                }
+        if let Some(index) = items.index_of(&key.to_string())
+            && index > 0 {
+                items.move_index(index, index - 1);
             }
-        });
     });
 
     let move_down = callback!([items] |key: &str| {
-        items.mutate(|items| {
-            if let Some(index) = items.get_index_of(key) {
-                if index < items.len() - 1 {
-                    items.swap_indices(index, index + 1);
-                }
+        if let Some(index) = items.index_of(&key.to_string())
+            && index < items.len() - 1 {
+                items.move_index(index, index + 1);
             }
-        });
     });
 
     let set_content = callback!([items] |key: &str, content: String| {
-        items.mutate(|items| {
-            items[key] = content;
-        });
+        items.insert(key.to_string(), content);
     });
 
     layout! {
         Div(.class = "todo".to_string()) {
             Div(.class = "todo-input".to_string()) {
-                Input(
-                    .value = input_value.clone(),
-                    .on_value_change = callback!(move |value| input_value.set(value)),
-                )
+                Input(.bind = Some(content.clone()))
                 Button(.on_click = add) {
                     Text("Add")
                 }
@@ -163,6 +161,8 @@ fn TodoList() -> Element {
     }
 }
 
+type SetContent = Shared<dyn Fn(&str, String)>;
+
 #[props]
 struct TodoListItemProps {
     data: (String, String),
@@ -175,7 +175,7 @@ struct TodoListItemProps {
     #[props(raw)]
     move_down: Shared<dyn Fn(&str)>,
     #[props(raw)]
-    set_content: Shared<dyn Fn(&str, String)>,
+    set_content: SetContent,
 }
 
 #[component]
@@ -0,0 +1,51 @@
+use nestix::Renderer;
+use web_sys::Node;
+
+/// Describes a DOM node created by [`DomRenderer`].
+pub enum DomNode {
+    Element(&'static str),
+    Text(String),
+}
+
+/// Renders host components into the browser DOM.
+pub struct DomRenderer;
+
+impl Renderer for DomRenderer {
+    type Node = Node;
+    type NodeDesc = DomNode;
+
+    fn create_node(&self, desc: DomNode) -> Node {
+        let document = web_sys::window().unwrap().document().unwrap();
+        match desc {
+            DomNode::Element(tag) => document.create_element(tag).unwrap().into(),
+            DomNode::Text(text) => document.create_text_node(&text).into(),
+        }
+    }
+
+    fn insert_after(&self, parent: &Node, pred: &Node, node: &Node) {
+        parent
+            .insert_before(node, pred.next_sibling().as_ref())
+            .unwrap();
+    }
+
+    fn prepend(&self, parent: &Node, node: &Node) {
+        parent
+            .insert_before(node, parent.first_child().as_ref())
+            .unwrap();
+    }
+
+    fn remove(&self, node: &Node) {
+        if let Some(parent) = node.parent_node() {
+            parent.remove_child(node).unwrap();
+        }
+    }
+
+    fn move_node(&self, parent: &Node, pred: Option<&Node>, node: &Node) {
+        // Inserting an attached DOM node moves it, so it does not need to be
+        // removed first.
+        match pred {
+            Some(pred) => self.insert_after(parent, pred, node),
+            None => self.prepend(parent, node),
+        }
+    }
+}
@@ -10,7 +10,7 @@ use crate::util::nestix_path;
 pub fn build_props(input: TokenStream) -> TokenStream {
     let props_input = parse_macro_input!(input as PropsInput);
     generate_build_props(&props_input)
-        .unwrap_or_else(|err| TokenStream2::from(err.to_compile_error()))
+        .unwrap_or_else(|err| err.to_compile_error())
         .into()
 }
 
@@ -213,29 +213,6 @@ fn parse_props_body(input: syn::parse::ParseStream) -> syn::Result<PropsBody> {
     Ok(PropsBody { start, named })
 }
 
-#[cfg(test)]
-mod tests {
-    use super::PropsInput;
-
-    #[test]
-    fn nested_property_must_be_followed_by_a_comma() {
-        let result = syn::parse_str::<PropsInput>(
-            "FlexViewProps(.view(.width = 120, .height = 6) .bg_color = Color::RED)",
-        );
-
-        let error = result.err().expect("missing comma should be rejected");
-        assert_eq!(error.to_string(), "expected `,` between properties");
-    }
-
-    #[test]
-    fn nested_property_followed_by_a_comma_is_accepted() {
-        syn::parse_str::<PropsInput>(
-            "FlexViewProps(.view(.width = 120, .height = 6), .bg_color = Color::RED)",
-        )
-        .expect("comma-separated properties should be accepted");
-    }
-}
-
 fn generate_build_props(input: &PropsInput) -> Result<TokenStream2, syn::Error> {
     let nestix_path = nestix_path();
 
@@ -262,3 +239,26 @@ fn generate_build_props(input: &PropsInput) -> Result<TokenStream2, syn::Error>
             .build()
     })
 }
+
+#[cfg(test)]
+mod tests {
+    use super::PropsInput;
+
+    #[test]
+    fn nested_property_must_be_followed_by_a_comma() {
+        let result = syn::parse_str::<PropsInput>(
+            "FlexViewProps(.view(.width = 120, .height = 6) .bg_color = Color::RED)",
+        );
+
+        let error = result.err().expect("missing comma should be rejected");
+        assert_eq!(error.to_string(), "expected `,` between properties");
+    }
+
+    #[test]
+    fn nested_property_followed_by_a_comma_is_accepted() {
+        syn::parse_str::<PropsInput>(
+            "FlexViewProps(.view(.width = 120, .height = 6), .bg_color = Color::RED)",
+        )
+        .expect("comma-separated properties should be accepted");
+    }
+}
@@ -11,7 +11,7 @@ use crate::{
 pub fn callback(input: TokenStream) -> TokenStream {
     let closure_input = parse_macro_input!(input as ClosureInput);
     generate_callback(closure_input)
-        .unwrap_or_else(|err| TokenStream2::from(err.to_compile_error()))
+        .unwrap_or_else(|err| err.to_compile_error())
         .into()
 }
 
@@ -45,7 +45,7 @@ pub fn generate_clone_var(input: &CloneVar) -> Result<TokenStream, syn::Error> {
             let #ident = #expr.clone();
         })
     } else {
-        let ident = if let Some(ident) = get_ident_from_expr(&expr) {
+        let ident = if let Some(ident) = get_ident_from_expr(expr) {
             ident
         } else {
             return Err(syn::Error::new(expr.span(), "explicit identifier needed"));
@@ -11,7 +11,7 @@ use crate::clone_var::{CloneVar, generate_clone_var};
 pub fn closure(input: TokenStream) -> TokenStream {
     let closure_input = parse_macro_input!(input as ClosureInput);
     generate_closure(closure_input)
-        .unwrap_or_else(|err| TokenStream2::from(err.to_compile_error()))
+        .unwrap_or_else(|err| err.to_compile_error())
         .into()
 }
 
@@ -58,14 +58,15 @@ pub fn generate_closure(input: ClosureInput) -> Result<TokenStream2, syn::Error>
     };
 
     let mut closure_tokens = input.closure_tokens;
-    if let Some(expr_closure) = input.expr_closure {
-        if expr_closure.capture.is_none() && has_clone_vars {
-            // Captured variables were cloned into fresh locals above; `move`
-            // makes the closure own those locals instead of borrowing them.
-            closure_tokens = quote! {
-                move #closure_tokens
-            };
-        }
+    if let Some(expr_closure) = input.expr_closure
+        && expr_closure.capture.is_none()
+        && has_clone_vars
+    {
+        // Captured variables were cloned into fresh locals above; `move`
+        // makes the closure own those locals instead of borrowing them.
+        closure_tokens = quote! {
+            move #closure_tokens
+        };
     }
 
     Ok(quote! {{
@@ -13,7 +13,7 @@ pub fn component(attr: TokenStream, input: TokenStream) -> TokenStream {
     let attr = parse_macro_input!(attr as PropsAttr);
     match syn::parse::<ItemFn>(input) {
         Ok(item) => generate_component(&raw, &attr, &item)
-            .unwrap_or_else(|err| TokenStream2::from(err.to_compile_error()))
+            .unwrap_or_else(|err| err.to_compile_error())
             .into(),
         Err(_) => raw.into(),
     }
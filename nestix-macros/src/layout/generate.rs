@@ -397,39 +397,38 @@ pub fn generate_layout(input: LayoutInput) -> Result<TokenStream, syn::Error> {
         generate_layout_item(&mut ctx, item)?;
     }
 
-    if items.len() == 1 {
-        if let LayoutItem::If(item_if) = &items[0] {
-            if item_if.is_single_item() {
-                let mut element_defs = TokenStream::new();
-                let mut computed_element_defs = TokenStream::new();
-
-                for (ident, element_output) in ctx.element_outputs {
-                    quote! {
-                        let #ident = #element_output;
-                    }
-                    .to_tokens(&mut element_defs);
-                }
-
-                for (ident, element_output) in ctx.computed_element_outputs {
-                    quote! {
-                        let #ident = #element_output;
-                    }
-                    .to_tokens(&mut computed_element_defs);
-                }
-
-                let direct_output = ctx.direct_output;
+    if items.len() == 1
+        && let LayoutItem::If(item_if) = &items[0]
+        && item_if.is_single_item()
+    {
+        let mut element_defs = TokenStream::new();
+        let mut computed_element_defs = TokenStream::new();
+
+        for (ident, element_output) in ctx.element_outputs {
+            quote! {
+                let #ident = #element_output;
+            }
+            .to_tokens(&mut element_defs);
+        }
 
-                return Ok(quote! {{
-                    #element_defs
-                    #nestix_path::computed(#nestix_path::closure!(
-                        move || {
-                            #computed_element_defs
-                            #direct_output
-                        }
-                    ))
-                }});
+        for (ident, element_output) in ctx.computed_element_outputs {
+            quote! {
+                let #ident = #element_output;
             }
+            .to_tokens(&mut computed_element_defs);
         }
+
+        let direct_output = ctx.direct_output;
+
+        return Ok(quote! {{
+            #element_defs
+            #nestix_path::computed(#nestix_path::closure!(
+                move || {
+                    #computed_element_defs
+                    #direct_output
+                }
+            ))
+        }});
     }
 
     match (
@@ -8,6 +8,7 @@
 //! - `#[props]` turns a named-field struct into a prop container and builder.
 //! - `layout!`, `callback!`, `computed!`, `destructure!`, and friends keep
 //!   component bodies compact while still expanding to normal runtime APIs.
+//! - `#[derive(Store)]` splits a struct into separately tracked fields.
 //!
 //! ```ignore
 //! use nestix::{Element, callback, component, create_state, layout};
@@ -38,6 +39,7 @@ mod layout;
 mod prop_value;
 mod props;
 mod signals;
+mod store;
 mod util;
 
 /// Builds a closure after cloning the requested captures.
@@ -202,6 +204,19 @@ pub fn computed(input: TokenStream) -> TokenStream {
     signals::computed(input)
 }
 
+/// Creates a computed signal with equality cutoff from closure syntax.
+///
+/// This wraps the runtime `nestix::memo` function after applying [`closure!`]
+/// capture handling. Dependents are only notified when the value changes.
+///
+/// ```ignore
+/// let is_even = memo!([count] || count.get() % 2 == 0);
+/// ```
+#[proc_macro]
+pub fn memo(input: TokenStream) -> TokenStream {
+    signals::memo(input)
+}
+
 /// Registers an effect from closure syntax.
 ///
 /// This wraps the runtime `nestix::effect` function after applying [`closure!`]
@@ -228,3 +243,26 @@ pub fn effect(input: TokenStream) -> TokenStream {
 pub fn scoped_effect(input: TokenStream) -> TokenStream {
     signals::scoped_effect(input)
 }
+
+/// Derives `Store` for a struct with named fields.
+///
+/// The derive generates a `<Name>Store` handle with an accessor per field.
+/// Plain fields are exposed as `State` signals, and fields marked
+/// `#[store(nested)]` are exposed as the store handle of their own type. The
+/// handle also provides `get`, `set`, and `update` for the whole value.
+///
+/// ```ignore
+/// #[derive(Clone, PartialEq, Store)]
+/// struct AppState {
+///     #[store(nested)]
+///     user: User,
+///     count: i32,
+/// }
+///
+/// let store = create_store(app_state);
+/// let name = computed!([store] || store.user().name().get());
+/// ```
+#[proc_macro_derive(Store, attributes(store))]
+pub fn derive_store(input: TokenStream) -> TokenStream {
+    store::derive_store(input)
+}
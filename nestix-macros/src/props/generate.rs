@@ -148,10 +148,7 @@ fn preprocess(input: ItemStruct, attr: PropsAttr) -> Result<Context, syn::Error>
         if !field_feature.nested && !field_feature.raw {
             let ty = &field.ty;
             let path = parse_quote!(#nestix_path::PropValue<#ty>);
-            field.ty = Type::Path(TypePath {
-                qself: None,
-                path: path,
-            });
+            field.ty = Type::Path(TypePath { qself: None, path });
         }
 
         field_features.push(field_feature);
@@ -298,10 +295,7 @@ fn generate_builder(ctx: &Context) -> Result<TokenStream, syn::Error> {
 
         if !field_feature.start && !field_feature.default {
             let path = parse_quote!(Option<#field_ty>);
-            field.ty = Type::Path(TypePath {
-                qself: None,
-                path: path,
-            });
+            field.ty = Type::Path(TypePath { qself: None, path });
         }
         field.vis = Visibility::Inherited;
     }
@@ -743,6 +737,7 @@ fn generate_builder(ctx: &Context) -> Result<TokenStream, syn::Error> {
     };
 
     Ok(quote! {
+        #[allow(clippy::type_complexity)]
         #vis mod #builder_mod_ident {
             use super::*;
 
@@ -13,6 +13,6 @@ pub fn props(
     let item_struct = parse_macro_input!(input as ItemStruct);
 
     generate_props(item_struct, attr)
-        .unwrap_or_else(|err| proc_macro2::TokenStream::from(err.to_compile_error()))
+        .unwrap_or_else(|err| err.to_compile_error())
         .into()
 }
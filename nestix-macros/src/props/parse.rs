@@ -70,6 +70,7 @@ impl Parse for PropsAttr {
     }
 }
 
+#[derive(Default)]
 pub struct PropsFieldAttr {
     pub default: Option<Ident>,
     pub default_value: Option<Expr>,
@@ -84,18 +85,6 @@ pub struct Nested {
     pub inputs: Option<Punctuated<FnArg, Token![,]>>,
 }
 
-impl Default for PropsFieldAttr {
-    fn default() -> Self {
-        Self {
-            default: None,
-            default_value: None,
-            start: None,
-            nested: None,
-            raw: None,
-        }
-    }
-}
-
 impl PropsFieldAttr {
     pub fn merge(mut self, other: PropsFieldAttr) -> Self {
         self.default = match (self.default, other.default) {
@@ -143,7 +132,7 @@ impl Parse for PropsFieldAttr {
 
                     if input.peek(Token![=]) {
                         input.parse::<Token![=]>()?;
-                        let expr = Expr::parse_without_eager_brace(&input)?;
+                        let expr = Expr::parse_without_eager_brace(input)?;
                         attr.default_value = Some(expr);
                     }
                 }
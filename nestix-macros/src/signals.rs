@@ -15,6 +15,16 @@ pub fn computed(input: TokenStream) -> TokenStream {
     .into()
 }
 
+pub fn memo(input: TokenStream) -> TokenStream {
+    let nestix_path = nestix_path();
+    let input = TokenStream2::from(input);
+
+    quote! {
+        #nestix_path::memo(#nestix_path::closure!(#input))
+    }
+    .into()
+}
+
 pub fn effect(input: TokenStream) -> TokenStream {
     let nestix_path = nestix_path();
     let input = TokenStream2::from(input);
@@ -0,0 +1,179 @@
+use proc_macro::TokenStream;
+use proc_macro2::TokenStream as TokenStream2;
+use quote::{format_ident, quote};
+use syn::{Data, DeriveInput, Fields, Ident, Type, parse_macro_input, spanned::Spanned};
+
+use crate::util::nestix_path;
+
+struct StoreField {
+    ident: Ident,
+    ty: Type,
+    nested: bool,
+}
+
+pub fn derive_store(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    generate_store(input)
+        .unwrap_or_else(|err| err.to_compile_error())
+        .into()
+}
+
+fn parse_fields(input: &DeriveInput) -> syn::Result<Vec<StoreField>> {
+    let Data::Struct(data) = &input.data else {
+        return Err(syn::Error::new(
+            input.ident.span(),
+            "Store can only be derived for structs",
+        ));
+    };
+    let Fields::Named(fields) = &data.fields else {
+        return Err(syn::Error::new(
+            data.fields.span(),
+            "Store can only be derived for structs with named fields",
+        ));
+    };
+
+    fields
+        .named
+        .iter()
+        .map(|field| {
+            let mut nested = false;
+            for attr in field
+                .attrs
+                .iter()
+                .filter(|attr| attr.path().is_ident("store"))
+            {
+                attr.parse_nested_meta(|meta| {
+                    if meta.path.is_ident("nested") {
+                        nested = true;
+                        Ok(())
+                    } else {
+                        Err(meta.error("unknown attribute"))
+                    }
+                })?;
+            }
+            Ok(StoreField {
+                ident: field.ident.clone().unwrap(),
+                ty: field.ty.clone(),
+                nested,
+            })
+        })
+        .collect()
+}
+
+fn generate_store(input: DeriveInput) -> syn::Result<TokenStream2> {
+    if !input.generics.params.is_empty() {
+        return Err(syn::Error::new(
+            input.generics.span(),
+            "Store cannot be derived for generic structs",
+        ));
+    }
+
+    let nestix_path = nestix_path();
+    let fields = parse_fields(&input)?;
+    let vis = &input.vis;
+    let ident = &input.ident;
+    let handle_ident = format_ident!("{}Store", ident);
+    let handle_doc = format!(
+        "A store handle for [`{ident}`] that tracks each field separately.\n\n\
+         Created with `create_store`."
+    );
+
+    let field_idents = fields.iter().map(|field| &field.ident).collect::<Vec<_>>();
+    let field_types = fields
+        .iter()
+        .map(|field| {
+            let ty = &field.ty;
+            if field.nested {
+                quote!(<#ty as #nestix_path::Store>::Handle)
+            } else {
+                quote!(#nestix_path::State<#ty>)
+            }
+        })
+        .collect::<Vec<_>>();
+    let field_inits = fields.iter().map(|field| {
+        let ident = &field.ident;
+        if field.nested {
+            quote!(#nestix_path::Store::into_store(value.#ident))
+        } else {
+            quote!(#nestix_path::create_state(value.#ident))
+        }
+    });
+    let accessor_docs = fields
+        .iter()
+        .map(|field| format!("Returns the handle for the `{}` field.", field.ident));
+
+    Ok(quote! {
+        #[doc = #handle_doc]
+        #vis struct #handle_ident {
+            #(#field_idents: #field_types,)*
+        }
+
+        impl #handle_ident {
+            #(
+                #[doc = #accessor_docs]
+                #vis fn #field_idents(&self) -> #field_types {
+                    self.#field_idents.clone()
+                }
+            )*
+
+            /// Returns a snapshot of the whole value.
+            ///
+            /// Inside an effect or computed value, this subscribes to every
+            /// field.
+            #vis fn get(&self) -> #ident {
+                #ident {
+                    #(#field_idents: self.#field_idents.get(),)*
+                }
+            }
+
+            /// Replaces the whole value.
+            ///
+            /// Only the readers of fields whose values changed are notified.
+            #[track_caller]
+            #vis fn set(&self, value: #ident) {
+                #nestix_path::batch(|| {
+                    #(self.#field_idents.set(value.#field_idents);)*
+                });
+            }
+
+            /// Replaces the whole value with the result of `updater`.
+            #[track_caller]
+            #vis fn update(&self, updater: impl FnOnce(&#ident) -> #ident) {
+                let value = #nestix_path::untrack(|| self.get());
+                self.set(updater(&value));
+            }
+        }
+
+        impl ::std::clone::Clone for #handle_ident {
+            fn clone(&self) -> Self {
+                Self {
+                    #(#field_idents: self.#field_idents.clone(),)*
+                }
+            }
+        }
+
+        impl #nestix_path::Signal for #handle_ident {
+            type Output = #ident;
+
+            fn get(&self) -> #ident {
+                self.get()
+            }
+
+            fn box_clone(&self) -> ::std::boxed::Box<dyn #nestix_path::Signal<Output = #ident>> {
+                ::std::boxed::Box::new(self.clone())
+            }
+        }
+
+        impl #nestix_path::Store for #ident {
+            type Handle = #handle_ident;
+
+            #[track_caller]
+            fn into_store(self) -> #handle_ident {
+                let value = self;
+                #handle_ident {
+                    #(#field_idents: #field_inits,)*
+                }
+            }
+        }
+    })
+}
@@ -0,0 +1,74 @@
+use std::cell::RefCell;
+
+use crate::{Effect, Scope, shared::Shared, untrack};
+
+thread_local! {
+    static CLEANUP_OWNER: RefCell<Option<CleanupOwner>> = const { RefCell::new(None) };
+}
+
+/// A callback registered with [`on_cleanup`].
+pub(crate) type Cleanup = Box<dyn FnOnce()>;
+
+/// The innermost effect, computed value, or scope that is currently running.
+#[derive(Clone)]
+pub(crate) enum CleanupOwner {
+    Effect(Shared<Effect>),
+    Scope(Scope),
+}
+
+pub(crate) struct CleanupOwnerGuard(Option<CleanupOwner>);
+
+impl CleanupOwnerGuard {
+    pub(crate) fn set(owner: Option<CleanupOwner>) -> Self {
+        Self(CLEANUP_OWNER.replace(owner))
+    }
+}
+
+impl Drop for CleanupOwnerGuard {
+    fn drop(&mut self) {
+        CLEANUP_OWNER.replace(self.0.take());
+    }
+}
+
+/// Runs `cleanups` in registration order without recording dependencies.
+pub(crate) fn run_cleanups(cleanups: Vec<Cleanup>) {
+    if cleanups.is_empty() {
+        return;
+    }
+    untrack(|| {
+        for cleanup in cleanups {
+            cleanup();
+        }
+    });
+}
+
+/// Registers `f` to run when the current computation is torn down.
+///
+/// Inside an effect or computed value, `f` runs before the next rerun and
+/// when the effect is canceled. Inside [`Scope::run`] but outside of any
+/// effect, `f` runs when the scope is disposed. Elsewhere, `f` is dropped
+/// without being called.
+///
+/// ```
+/// use nestix_signal::{create_state, effect, on_cleanup};
+///
+/// let count = create_state(0);
+/// let handle = effect({
+///     let count = count.clone();
+///     move || {
+///         let current = count.get();
+///         on_cleanup(move || println!("leaving {current}"));
+///     }
+/// });
+///
+/// count.set(1); // prints "leaving 0"
+/// handle.cancel(); // prints "leaving 1"
+/// ```
+pub fn on_cleanup(f: impl FnOnce() + 'static) {
+    let owner = CLEANUP_OWNER.with_borrow(|owner| owner.clone());
+    match owner {
+        Some(CleanupOwner::Effect(effect)) => effect.add_cleanup(Box::new(f)),
+        Some(CleanupOwner::Scope(scope)) => scope.add_cleanup(Box::new(f)),
+        None => {}
+    }
+}
@@ -2,17 +2,34 @@ use std::{
     cell::{Cell, RefCell},
     collections::HashSet,
     panic::Location,
-    rc::Rc,
+    rc::{Rc, Weak},
 };
 
-use crate::{Effect, Readonly, Shared, Signal, current_effect, notify_effect, set_current_effect};
+use crate::{
+    CleanupOwner, CleanupOwnerGuard, CurrentEffectGuard, DependencySet, Effect, GraphNodeKind,
+    Readonly, Scope, ScopeGuard, Shared, Signal, current_effect, notify_dependents, register_node,
+    run_effect,
+};
 
 struct ComputedData<T> {
     cached: RefCell<Option<T>>,
     dirty: Rc<Cell<bool>>,
-    dependents: Shared<RefCell<HashSet<Shared<Effect>>>>,
+    dependents: DependencySet,
     runner: Shared<Effect>,
     compute: Rc<dyn Fn() -> T>,
+    cutoff: Option<Cutoff>,
+    /// Owns the effects and computed values created by the last evaluation.
+    scope: RefCell<Option<Scope>>,
+}
+
+/// The eager half of a computed value created with [`Computed::with_eq`].
+///
+/// `checker` is the only dependent of the invalidation runner. It runs as a
+/// regular effect, recomputes the value once its sources have settled, and
+/// notifies `dependents` only when the value changed.
+struct Cutoff {
+    dependents: DependencySet,
+    checker: Shared<Effect>,
 }
 
 /// A lazily evaluated value derived from other signals.
@@ -27,11 +44,12 @@ pub struct Computed<T> {
 impl<T: Clone> Computed<T> {
     /// Reads the current computed value, re-evaluating it if it is dirty.
     pub fn get(&self) -> T {
-        if let Some(effect) = current_effect() {
-            effect.add_dependency_set(self.data.dependents.clone());
-            self.data.dependents.borrow_mut().insert(effect);
+        let value = self.evaluate();
+        match &self.data.cutoff {
+            Some(cutoff) => track(&cutoff.dependents, &cutoff.checker),
+            None => track(&self.data.dependents, &self.data.runner),
         }
-        self.evaluate()
+        value
     }
 
     fn evaluate(&self) -> T {
@@ -41,10 +59,23 @@ impl<T: Clone> Computed<T> {
                 dependency_set.borrow_mut().remove(&self.data.runner);
             }
 
-            let prev = current_effect();
-            set_current_effect(Some(self.data.runner.clone()));
+            // What the last evaluation created is disposed now rather than on
+            // invalidation, so it stays alive for as long as its value is read.
+            // The new scope is nested in the scope this computed value was
+            // created in rather than in whichever effect reads it.
+            if let Some(scope) = self.data.scope.take() {
+                scope.dispose();
+            }
+            let scope = Scope::new_in(self.data.runner.owner());
+            self.data.scope.replace(Some(scope.clone()));
+
+            let guard = (
+                CurrentEffectGuard::set(Some(self.data.runner.clone())),
+                CleanupOwnerGuard::set(Some(CleanupOwner::Effect(self.data.runner.clone()))),
+                ScopeGuard::set(Some(scope)),
+            );
             self.data.cached.replace(Some((self.data.compute)()));
-            set_current_effect(prev);
+            drop(guard);
 
             self.data.dirty.set(false);
         }
@@ -53,6 +84,73 @@ impl<T: Clone> Computed<T> {
     }
 }
 
+impl<T: Clone + 'static> Computed<T> {
+    /// Creates a computed value that only notifies dependents when its value
+    /// changes according to `eq`.
+    ///
+    /// Unlike [`computed`], the value is recomputed eagerly after any of its
+    /// dependencies changes, so that unchanged results can stop propagation.
+    /// Reads inside a [`batch`](crate::batch) still observe the current value.
+    #[track_caller]
+    pub fn with_eq(
+        compute: impl Fn() -> T + 'static,
+        eq: impl Fn(&T, &T) -> bool + 'static,
+    ) -> Self {
+        let location = Location::caller();
+        let dependents = Shared::new(RefCell::new(HashSet::new()));
+        let dirty = Rc::new(Cell::new(true));
+
+        let data = Rc::new_cyclic(|data: &Weak<ComputedData<T>>| {
+            let cutoff_dependents = Shared::new(RefCell::new(HashSet::new()));
+            let last = RefCell::new(None::<T>);
+            let checker = Effect::new(location, {
+                let data = data.clone();
+                let cutoff_dependents = cutoff_dependents.clone();
+                Shared::from(Rc::new(move || {
+                    let Some(data) = data.upgrade() else {
+                        return;
+                    };
+                    let computed = Computed { data };
+                    let value = computed.evaluate();
+                    track(&computed.data.dependents, &computed.data.runner);
+                    let changed = match &*last.borrow() {
+                        Some(last) => !eq(last, &value),
+                        None => false,
+                    };
+                    last.replace(Some(value));
+                    if changed {
+                        notify_dependents(&cutoff_dependents, location);
+                    }
+                }) as Rc<dyn Fn()>)
+            });
+
+            ComputedData {
+                cached: RefCell::new(None),
+                dirty: dirty.clone(),
+                dependents: dependents.clone(),
+                runner: invalidation_runner(location, dirty, dependents),
+                compute: Rc::new(compute),
+                cutoff: Some(Cutoff {
+                    dependents: cutoff_dependents,
+                    checker,
+                }),
+                scope: RefCell::new(None),
+            }
+        });
+
+        if let Some(cutoff) = &data.cutoff {
+            register_node(
+                GraphNodeKind::Computed,
+                location,
+                Some(&cutoff.dependents),
+                Some(&data.runner),
+            );
+            run_effect(&cutoff.checker, location);
+        }
+        Self { data }
+    }
+}
+
 impl<T> Clone for Computed<T> {
     fn clone(&self) -> Self {
         Self {
@@ -94,21 +192,16 @@ impl<T: Clone + 'static> Computed<T> {
 #[track_caller]
 pub fn computed<T: 'static>(compute: impl Fn() -> T + 'static) -> Computed<T> {
     let location = Location::caller();
-    let compute = Rc::new(compute);
     let dependents = Shared::new(RefCell::new(HashSet::new()));
     let dirty = Rc::new(Cell::new(true));
 
-    let runner = Effect::new_unbatched(location, {
-        let dirty = dirty.clone();
-        let dependents = dependents.clone();
-        Shared::from(Rc::new(move || {
-            dirty.set(true);
-            let dependents = dependents.borrow().clone();
-            for effect in dependents {
-                notify_effect(&effect, location);
-            }
-        }) as Rc<dyn Fn()>)
-    });
+    let runner = invalidation_runner(location, dirty.clone(), dependents.clone());
+    register_node(
+        GraphNodeKind::Computed,
+        location,
+        Some(&dependents),
+        Some(&runner),
+    );
 
     Computed {
         data: Rc::new(ComputedData {
@@ -116,7 +209,44 @@ pub fn computed<T: 'static>(compute: impl Fn() -> T + 'static) -> Computed<T> {
             dirty,
             dependents,
             runner,
-            compute,
+            compute: Rc::new(compute),
+            cutoff: None,
+            scope: RefCell::new(None),
         }),
     }
 }
+
+/// Creates a computed value that only notifies dependents when its value
+/// changes.
+///
+/// This is [`Computed::with_eq`] using [`PartialEq`] to compare values.
+#[track_caller]
+pub fn memo<T: Clone + PartialEq + 'static>(compute: impl Fn() -> T + 'static) -> Computed<T> {
+    Computed::with_eq(compute, T::eq)
+}
+
+/// Subscribes the current effect, if any, to `dependents`, which are notified
+/// by `source`.
+fn track(dependents: &DependencySet, source: &Shared<Effect>) {
+    if let Some(effect) = current_effect() {
+        effect.add_dependency_set(dependents.clone());
+        effect.raise_height(source.height() + 1);
+        dependents.borrow_mut().insert(effect);
+    }
+}
+
+/// Creates the effect that marks a computed value dirty and notifies its
+/// dependents when one of its sources changes.
+fn invalidation_runner(
+    location: &'static Location<'static>,
+    dirty: Rc<Cell<bool>>,
+    dependents: DependencySet,
+) -> Shared<Effect> {
+    Effect::new_unbatched(
+        location,
+        Shared::from(Rc::new(move || {
+            dirty.set(true);
+            notify_dependents(&dependents, location);
+        }) as Rc<dyn Fn()>),
+    )
+}
@@ -24,10 +24,74 @@ pub struct DebugConfig {
     /// Warns when an effect attempts to trigger itself while it is already
     /// running.
     pub detect_cyclic: bool,
+    /// Records states, computed values, and effects as they are created so
+    /// they can be inspected with [`signal_graph`](crate::signal_graph).
+    pub track_graph: bool,
+}
+
+impl Default for DebugConfig {
+    fn default() -> Self {
+        default_config()
+    }
 }
 
 const fn default_config() -> DebugConfig {
     DebugConfig {
         detect_cyclic: false,
+        track_graph: false,
+    }
+}
+
+static CYCLE_CONFIG: RwLock<CycleConfig> = RwLock::new(default_cycle_config());
+
+/// Configures how the signal runtime handles effects that keep triggering
+/// themselves.
+///
+/// Unlike [`debug_signals`], this also applies in release builds.
+pub fn set_cycle_config(config: CycleConfig) {
+    let mut write = CYCLE_CONFIG.write().unwrap();
+    *write = config;
+}
+
+pub(crate) fn get_cycle_config() -> CycleConfig {
+    let read = CYCLE_CONFIG.read().unwrap();
+    *read
+}
+
+/// What to do when an effect update cycle is detected.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CyclePolicy {
+    /// Lets effects rerun themselves, and logs a warning and stops rerunning
+    /// an effect once it exceeds [`CycleConfig::max_reruns`].
+    Warn,
+    /// Silently skips reruns of an effect triggered by its own writes or in
+    /// excess of [`CycleConfig::max_reruns`].
+    Skip,
+    /// Panics with the chain of writes that led to the cycle as soon as an
+    /// effect is triggered by its own writes or exceeds
+    /// [`CycleConfig::max_reruns`].
+    Panic,
+}
+
+/// Runtime options for cycle detection.
+#[derive(Debug, Clone, Copy)]
+pub struct CycleConfig {
+    /// What to do when a cycle is detected.
+    pub policy: CyclePolicy,
+    /// How many times an effect may run while applying a single batch of
+    /// updates before it is considered to be in a cycle.
+    pub max_reruns: usize,
+}
+
+impl Default for CycleConfig {
+    fn default() -> Self {
+        default_cycle_config()
+    }
+}
+
+const fn default_cycle_config() -> CycleConfig {
+    CycleConfig {
+        policy: CyclePolicy::Warn,
+        max_reruns: 100,
     }
 }
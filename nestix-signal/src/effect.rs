@@ -1,18 +1,29 @@
 use std::{
     cell::{Cell, RefCell},
-    collections::HashSet,
-    panic::Location,
+    cmp::Ordering,
+    collections::{BinaryHeap, HashMap, HashSet},
+    fmt::Write,
+    panic::{AssertUnwindSafe, Location},
     rc::Rc,
 };
 
-use crate::{WeakShared, get_config, shared::Shared};
+use crate::{
+    CaughtPanic, Cleanup, CleanupOwner, CleanupOwnerGuard, CyclePolicy, ErrorHandler,
+    ErrorHandlerGuard, GraphNodeKind, Scope, ScopeGuard, SuspenseGuard, SuspenseTracker,
+    WeakShared, current_error_handler, current_scope, current_suspense, get_config,
+    get_cycle_config, register_node, run_cleanups, schedule_flush, shared::Shared,
+};
 
 thread_local! {
-    static CURRENT_EFFECT: RefCell<Option<Shared<Effect>>> = RefCell::new(None);
-    static RUNNING_EFFECTS: RefCell<HashSet<Shared<Effect>>> = RefCell::new(HashSet::new());
+    static CURRENT_EFFECT: RefCell<Option<Shared<Effect>>> = const { RefCell::new(None) };
+    static RUNNING_EFFECTS: RefCell<Vec<Shared<Effect>>> = const { RefCell::new(Vec::new()) };
     static BATCH_DEPTH: Cell<usize> = const { Cell::new(0) };
-    static PENDING_EFFECTS: RefCell<Vec<(Shared<Effect>, &'static Location<'static>)>> = const { RefCell::new(Vec::new()) };
+    static BATCH_ID: Cell<u64> = const { Cell::new(0) };
+    static PENDING_EFFECTS: RefCell<BinaryHeap<PendingEffect>> = const { RefCell::new(BinaryHeap::new()) };
+    static NEXT_PENDING_ORDER: Cell<u64> = const { Cell::new(0) };
     static PENDING_EFFECT_SET: RefCell<HashSet<Shared<Effect>>> = RefCell::new(HashSet::new());
+    static FLUSH_RUN_COUNTS: RefCell<HashMap<Shared<Effect>, usize>> = RefCell::new(HashMap::new());
+    static TRIGGERS: RefCell<HashMap<Shared<Effect>, Trigger>> = RefCell::new(HashMap::new());
 }
 
 pub(crate) fn current_effect() -> Option<Shared<Effect>> {
@@ -24,32 +35,93 @@ pub(crate) fn current_effect() -> Option<Shared<Effect>> {
     })
 }
 
-pub(crate) fn set_current_effect(effect: Option<Shared<Effect>>) {
-    CURRENT_EFFECT.replace(effect);
+/// Restores the previous current effect when dropped, including during
+/// unwinding.
+pub(crate) struct CurrentEffectGuard(Option<Shared<Effect>>);
+
+impl CurrentEffectGuard {
+    pub(crate) fn set(effect: Option<Shared<Effect>>) -> Self {
+        let prev = current_effect();
+        CURRENT_EFFECT.replace(effect);
+        Self(prev)
+    }
+}
+
+impl Drop for CurrentEffectGuard {
+    fn drop(&mut self) {
+        CURRENT_EFFECT.replace(self.0.take());
+    }
 }
 
 pub(crate) fn is_effect_running(effect: &Shared<Effect>) -> bool {
     RUNNING_EFFECTS.with_borrow(|effects| effects.contains(effect))
 }
 
-pub(crate) fn start_effect(effect: Shared<Effect>) {
-    RUNNING_EFFECTS.with_borrow_mut(|effects| effects.insert(effect));
+fn end_effect(effect: &Shared<Effect>) {
+    RUNNING_EFFECTS.with_borrow_mut(|effects| {
+        if let Some(index) = effects.iter().rposition(|running| running == effect) {
+            effects.remove(index);
+        }
+    });
 }
 
-pub(crate) fn end_effect(effect: &Shared<Effect>) {
-    RUNNING_EFFECTS.with_borrow_mut(|effects| effects.remove(effect));
+/// Returns the innermost running effect that is not a computed value.
+fn running_batched_effect() -> Option<Shared<Effect>> {
+    RUNNING_EFFECTS.with_borrow(|effects| {
+        effects
+            .iter()
+            .rev()
+            .find(|effect| effect.should_batch())
+            .cloned()
+    })
 }
 
-fn is_batching() -> bool {
-    BATCH_DEPTH.with(|depth| depth.get() > 0)
+/// Marks an effect as running and makes it the current effect until dropped.
+struct RunningEffectGuard {
+    effect: Shared<Effect>,
+    _current: CurrentEffectGuard,
+    _error_handler: ErrorHandlerGuard,
+    _suspense: SuspenseGuard,
+    _scope: ScopeGuard,
+    _cleanup_owner: CleanupOwnerGuard,
 }
 
+impl RunningEffectGuard {
+    fn start(effect: &Shared<Effect>) -> Self {
+        RUNNING_EFFECTS.with_borrow_mut(|effects| effects.push(effect.clone()));
+        Self {
+            effect: effect.clone(),
+            _current: CurrentEffectGuard::set(Some(effect.clone())),
+            _error_handler: ErrorHandlerGuard::set(effect.error_handler.clone()),
+            _suspense: SuspenseGuard::set(effect.suspense.clone()),
+            _scope: ScopeGuard::set(Some(effect.start_run_scope())),
+            _cleanup_owner: CleanupOwnerGuard::set(Some(CleanupOwner::Effect(effect.clone()))),
+        }
+    }
+}
+
+impl Drop for RunningEffectGuard {
+    fn drop(&mut self) {
+        end_effect(&self.effect);
+    }
+}
+
+/// The set of effects subscribed to a single signal.
+pub(crate) type DependencySet = Shared<RefCell<HashSet<Shared<Effect>>>>;
+
 pub(crate) struct Effect {
     location: &'static Location<'static>,
     callback: Shared<dyn Fn()>,
-    dependency_sets: RefCell<HashSet<Shared<RefCell<HashSet<Shared<Effect>>>>>>,
+    dependency_sets: RefCell<HashSet<DependencySet>>,
+    cleanups: RefCell<Vec<Cleanup>>,
     cancelled: Cell<bool>,
+    height: Cell<usize>,
     batched: bool,
+    error_handler: Option<ErrorHandler>,
+    suspense: Option<SuspenseTracker>,
+    owner: Option<Scope>,
+    /// Owns the effects and computed values created during the current run.
+    run_scope: RefCell<Option<Scope>>,
 }
 
 impl Effect {
@@ -66,25 +138,83 @@ impl Effect {
         callback: Shared<dyn Fn()>,
         batched: bool,
     ) -> Shared<Self> {
-        Shared::new(Effect {
+        let owner = current_scope();
+        let effect = Shared::new(Effect {
             location,
             callback,
             dependency_sets: RefCell::new(HashSet::new()),
+            cleanups: RefCell::new(Vec::new()),
             cancelled: Cell::new(false),
+            height: Cell::new(0),
             batched,
-        })
+            error_handler: current_error_handler(),
+            suspense: current_suspense(),
+            owner: owner.clone(),
+            run_scope: RefCell::new(None),
+        });
+        if let Some(owner) = owner {
+            owner.own_effect(&effect);
+        }
+        effect
     }
 
-    pub fn add_dependency_set(&self, dependency_set: Shared<RefCell<HashSet<Shared<Effect>>>>) {
+    pub fn add_dependency_set(&self, dependency_set: DependencySet) {
         if !self.is_cancelled() {
             self.dependency_sets.borrow_mut().insert(dependency_set);
+            self.raise_height(1);
         }
     }
 
-    pub fn take_dependency_sets(&self) -> HashSet<Shared<RefCell<HashSet<Shared<Effect>>>>> {
+    /// Returns the tracked dependency sets.
+    pub(crate) fn dependency_sets(&self) -> Vec<DependencySet> {
+        self.dependency_sets.borrow().iter().cloned().collect()
+    }
+
+    /// Takes the tracked dependency sets and resets the height, which is
+    /// recomputed from the dependencies read during the next run.
+    pub fn take_dependency_sets(&self) -> HashSet<DependencySet> {
+        self.height.set(0);
         self.dependency_sets.take()
     }
 
+    /// Returns the length of the longest dependency chain from a state to
+    /// this effect.
+    pub fn height(&self) -> usize {
+        self.height.get()
+    }
+
+    /// Ensures this effect is scheduled after sources of height `height - 1`.
+    pub fn raise_height(&self, height: usize) {
+        if self.height.get() < height {
+            self.height.set(height);
+        }
+    }
+
+    pub(crate) fn add_cleanup(&self, cleanup: Cleanup) {
+        self.cleanups.borrow_mut().push(cleanup);
+    }
+
+    /// Disposes what the previous run created and then runs the cleanups it
+    /// registered.
+    pub(crate) fn run_cleanups(&self) {
+        if let Some(scope) = self.run_scope.take() {
+            scope.dispose();
+        }
+        run_cleanups(self.cleanups.take());
+    }
+
+    /// Creates the scope that owns what the next run creates.
+    fn start_run_scope(&self) -> Scope {
+        let scope = Scope::new_in(self.owner.clone());
+        self.run_scope.replace(Some(scope.clone()));
+        scope
+    }
+
+    /// Returns the scope this effect was created in.
+    pub(crate) fn owner(&self) -> Option<Scope> {
+        self.owner.clone()
+    }
+
     pub fn is_cancelled(&self) -> bool {
         self.cancelled.get()
     }
@@ -93,12 +223,17 @@ impl Effect {
         self.batched
     }
 
-    fn cancel(effect: &Shared<Effect>) {
-        effect.cancelled.set(true);
+    pub(crate) fn cancel(effect: &Shared<Effect>) {
+        if effect.cancelled.replace(true) {
+            return;
+        }
         for dependency_set in effect.take_dependency_sets() {
             dependency_set.borrow_mut().remove(effect);
         }
-        end_effect(effect);
+        if let Some(owner) = &effect.owner {
+            owner.disown_effect(effect);
+        }
+        effect.run_cleanups();
     }
 }
 
@@ -147,6 +282,7 @@ pub fn effect(f: impl Fn() + 'static) -> EffectHandle {
     let location = Location::caller();
     let callback = Shared::from(Rc::new(f) as Rc<dyn Fn()>);
     let effect = Effect::new(location, callback);
+    register_node(GraphNodeKind::Effect, location, None, Some(&effect));
     run_effect(&effect, location);
     EffectHandle::new(effect)
 }
@@ -164,7 +300,7 @@ impl Drop for BatchGuard {
         if is_outermost {
             if std::thread::panicking() {
                 clear_pending_effects();
-            } else {
+            } else if !schedule_flush() {
                 flush_pending_effects();
             }
         }
@@ -176,8 +312,17 @@ impl Drop for BatchGuard {
 /// Effects caused by state changes inside `f` are queued and run once after the
 /// outermost batch completes. Computed values are still invalidated
 /// immediately, so reads inside the batch observe current derived values.
+///
+/// Every write is batched on its own, so `batch` is only needed to group
+/// several writes. When a [`Scheduler`](crate::Scheduler) is installed, the
+/// queued effects run when it flushes instead.
 pub fn batch<T>(f: impl FnOnce() -> T) -> T {
-    BATCH_DEPTH.with(|depth| depth.set(depth.get() + 1));
+    BATCH_DEPTH.with(|depth| {
+        if depth.get() == 0 {
+            BATCH_ID.set(BATCH_ID.get() + 1);
+        }
+        depth.set(depth.get() + 1)
+    });
 
     let guard = BatchGuard;
     let value = f();
@@ -185,13 +330,129 @@ pub fn batch<T>(f: impl FnOnce() -> T) -> T {
     value
 }
 
-fn flush_pending_effects() {
-    let pending = PENDING_EFFECTS.with_borrow_mut(std::mem::take);
-    PENDING_EFFECT_SET.with_borrow_mut(|effects| effects.clear());
+/// Returns an identifier for the outermost batch in progress, if any.
+///
+/// Writes made by effects while a batch flushes share one identifier.
+pub(crate) fn current_batch() -> Option<u64> {
+    (BATCH_DEPTH.get() > 0).then(|| BATCH_ID.get())
+}
+
+pub(crate) fn has_pending_effects() -> bool {
+    PENDING_EFFECTS.with_borrow(|effects| !effects.is_empty())
+}
+
+/// An effect waiting for the current batch to flush.
+///
+/// Pending effects run in order of increasing height, so an effect runs after
+/// every computed value and effect it may read from has settled. Effects of
+/// the same height run in the order they were notified.
+struct PendingEffect {
+    height: usize,
+    order: u64,
+    effect: Shared<Effect>,
+    location: &'static Location<'static>,
+}
+
+impl PartialEq for PendingEffect {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for PendingEffect {}
+
+impl PartialOrd for PendingEffect {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for PendingEffect {
+    fn cmp(&self, other: &Self) -> Ordering {
+        // `BinaryHeap` is a max-heap, so the lowest pending effect compares
+        // as the greatest.
+        (other.height, other.order).cmp(&(self.height, self.order))
+    }
+}
+
+/// Keeps the batch open while pending effects run, so writes made by those
+/// effects are queued into the same flush.
+struct FlushGuard;
+
+impl FlushGuard {
+    fn start() -> Self {
+        BATCH_DEPTH.with(|depth| depth.set(1));
+        BATCH_ID.set(BATCH_ID.get() + 1);
+        Self
+    }
+}
+
+impl Drop for FlushGuard {
+    fn drop(&mut self) {
+        BATCH_DEPTH.with(|depth| depth.set(0));
+        FLUSH_RUN_COUNTS.with_borrow_mut(|counts| counts.clear());
+        TRIGGERS.with_borrow_mut(|triggers| triggers.clear());
+        if std::thread::panicking() {
+            clear_pending_effects();
+        }
+    }
+}
+
+pub(crate) fn flush_pending_effects() {
+    let _guard = FlushGuard::start();
+    let config = get_cycle_config();
+    while let Some(pending) = PENDING_EFFECTS.with_borrow_mut(|effects| effects.pop()) {
+        PENDING_EFFECT_SET.with_borrow_mut(|effects| effects.remove(&pending.effect));
+
+        let runs = FLUSH_RUN_COUNTS.with_borrow_mut(|counts| {
+            let runs = counts.entry(pending.effect.clone()).or_default();
+            *runs += 1;
+            *runs
+        });
+        if runs > config.max_reruns {
+            match config.policy {
+                CyclePolicy::Warn => log::warn!(
+                    "{}\nthe effect ran {} times in one update and will not run again \
+                     until the next one",
+                    cycle_chain(&pending.effect),
+                    config.max_reruns,
+                ),
+                CyclePolicy::Skip => {}
+                CyclePolicy::Panic => panic!("{}", cycle_chain(&pending.effect)),
+            }
+            continue;
+        }
+
+        run_effect(&pending.effect, pending.location);
+    }
+}
+
+/// Records which write notified a pending effect, and which effect made that
+/// write.
+#[derive(Clone)]
+struct Trigger {
+    location: &'static Location<'static>,
+    effect: Option<Shared<Effect>>,
+}
 
-    for (effect, location) in pending {
-        run_effect(&effect, location);
+/// Describes the chain of writes that last notified `effect`, following the
+/// effects that made them until the chain ends or loops.
+fn cycle_chain(effect: &Shared<Effect>) -> String {
+    let mut chain = format!("cyclic update detected\n\teffect at {}", effect.location);
+    let mut visited = HashSet::from([effect.clone()]);
+    let mut next = TRIGGERS.with_borrow(|triggers| triggers.get(effect).cloned());
+    while let Some(trigger) = next {
+        write!(chain, "\n\tnotified by a write at {}", trigger.location).unwrap();
+        let Some(effect) = trigger.effect else {
+            break;
+        };
+        write!(chain, "\n\tin effect at {}", effect.location).unwrap();
+        if !visited.insert(effect.clone()) {
+            break;
+        }
+        next = TRIGGERS.with_borrow(|triggers| triggers.get(&effect).cloned());
     }
+    chain
 }
 
 fn clear_pending_effects() {
@@ -199,14 +460,70 @@ fn clear_pending_effects() {
     PENDING_EFFECT_SET.with_borrow_mut(|effects| effects.clear());
 }
 
-pub(crate) fn notify_effect(effect: &Shared<Effect>, location: &'static Location<'static>) {
-    if is_batching() && effect.should_batch() {
-        let inserted = PENDING_EFFECT_SET.with_borrow_mut(|effects| effects.insert(effect.clone()));
-        if inserted {
-            PENDING_EFFECTS.with_borrow_mut(|effects| effects.push((effect.clone(), location)));
+/// Notifies every effect in `dependents` that a signal they read has changed.
+///
+/// The effects are scheduled as one batch, so each of them runs at most once
+/// and only after all computed values affected by the change are invalidated.
+pub(crate) fn notify_dependents(dependents: &DependencySet, location: &'static Location<'static>) {
+    let dependents = dependents.borrow().clone();
+    batch(|| {
+        for effect in dependents {
+            notify_effect(&effect, location);
         }
-    } else {
+    });
+}
+
+fn notify_effect(effect: &Shared<Effect>, location: &'static Location<'static>) {
+    if !effect.should_batch() {
         run_effect(effect, location);
+        return;
+    }
+
+    #[cfg(debug_assertions)]
+    warn_if_cyclic(effect, location);
+
+    TRIGGERS.with_borrow_mut(|triggers| {
+        triggers.insert(
+            effect.clone(),
+            Trigger {
+                location,
+                effect: running_batched_effect(),
+            },
+        )
+    });
+    if is_effect_running(effect) {
+        match get_cycle_config().policy {
+            CyclePolicy::Warn => {}
+            CyclePolicy::Skip => return,
+            CyclePolicy::Panic => panic!("{}", cycle_chain(effect)),
+        }
+    }
+
+    let inserted = PENDING_EFFECT_SET.with_borrow_mut(|effects| effects.insert(effect.clone()));
+    if inserted {
+        let order = NEXT_PENDING_ORDER.with(|order| order.replace(order.get() + 1));
+        PENDING_EFFECTS.with_borrow_mut(|effects| {
+            effects.push(PendingEffect {
+                height: effect.height(),
+                order,
+                effect: effect.clone(),
+                location,
+            })
+        });
+    }
+}
+
+#[cfg(debug_assertions)]
+fn warn_if_cyclic(effect: &Shared<Effect>, location: &'static Location<'static>) {
+    let config = get_config();
+    if config.detect_cyclic && is_effect_running(effect) {
+        log::warn!(
+            "cyclic update detected\n\tat {}:{}\nwhen trying to modify value\n\tat {}:{}",
+            effect.location.file(),
+            effect.location.line(),
+            location.file(),
+            location.line(),
+        );
     }
 }
 
@@ -216,29 +533,33 @@ pub(crate) fn run_effect(effect: &Shared<Effect>, location: &'static Location<'s
     }
 
     #[cfg(debug_assertions)]
-    {
-        let config = get_config();
-        if config.detect_cyclic && is_effect_running(effect) {
-            log::warn!(
-                "cyclic update detected\n\tat {}:{}\nwhen trying to modify value\n\tat {}:{}",
-                effect.location.file(),
-                effect.location.line(),
-                location.file(),
-                location.line(),
-            );
-        }
-    }
+    warn_if_cyclic(effect, location);
+
+    effect.run_cleanups();
 
     // Cleanup old dependencies
-    for dependency_set in effect.dependency_sets.take() {
+    for dependency_set in effect.take_dependency_sets() {
         dependency_set.borrow_mut().remove(effect);
     }
 
     // Execute effect
-    start_effect(effect.clone());
-    let prev = current_effect();
-    set_current_effect(Some(effect.clone()));
-    (effect.callback)();
-    set_current_effect(prev);
-    end_effect(effect);
+    let guard = RunningEffectGuard::start(effect);
+    let result = match &effect.error_handler {
+        Some(_) => std::panic::catch_unwind(AssertUnwindSafe(|| (effect.callback)())),
+        None => {
+            (effect.callback)();
+            Ok(())
+        }
+    };
+    drop(guard);
+
+    // Cleanups registered after the effect canceled itself would never run
+    // otherwise.
+    if effect.is_cancelled() {
+        effect.run_cleanups();
+    }
+
+    if let (Err(payload), Some(handler)) = (result, &effect.error_handler) {
+        handler(CaughtPanic::from_payload(payload));
+    }
 }
@@ -0,0 +1,86 @@
+use std::{
+    any::Any,
+    cell::RefCell,
+    fmt::{Debug, Display},
+    rc::Rc,
+};
+
+use crate::Shared;
+
+thread_local! {
+    static ERROR_HANDLER: RefCell<Option<ErrorHandler>> = const { RefCell::new(None) };
+}
+
+/// A callback that receives panics caught by the signal runtime.
+pub type ErrorHandler = Shared<dyn Fn(CaughtPanic)>;
+
+/// A panic caught while running an effect.
+///
+/// Cloning a `CaughtPanic` is cheap; clones share the same message.
+#[derive(Clone)]
+pub struct CaughtPanic {
+    message: Rc<str>,
+}
+
+impl CaughtPanic {
+    /// Creates a `CaughtPanic` from a payload returned by
+    /// [`std::panic::catch_unwind`].
+    pub fn from_payload(payload: Box<dyn Any + Send>) -> Self {
+        let message = if let Some(message) = payload.downcast_ref::<&str>() {
+            Rc::from(*message)
+        } else if let Some(message) = payload.downcast_ref::<String>() {
+            Rc::from(message.as_str())
+        } else {
+            Rc::from("Box<dyn Any>")
+        };
+        Self { message }
+    }
+
+    /// Returns the panic message, or `"Box<dyn Any>"` when the payload was not
+    /// a string.
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+}
+
+impl Debug for CaughtPanic {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_tuple("CaughtPanic").field(&self.message).finish()
+    }
+}
+
+impl Display for CaughtPanic {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.message)
+    }
+}
+
+/// Returns the error handler that effects created now would report to.
+pub fn current_error_handler() -> Option<ErrorHandler> {
+    ERROR_HANDLER.with_borrow(|handler| handler.clone())
+}
+
+pub(crate) struct ErrorHandlerGuard(Option<ErrorHandler>);
+
+impl ErrorHandlerGuard {
+    pub(crate) fn set(handler: Option<ErrorHandler>) -> Self {
+        Self(ERROR_HANDLER.replace(handler))
+    }
+}
+
+impl Drop for ErrorHandlerGuard {
+    fn drop(&mut self) {
+        ERROR_HANDLER.replace(self.0.take());
+    }
+}
+
+/// Runs `f` with `handler` as the current error handler.
+///
+/// Effects created while `f` runs, including effects created later by those
+/// effects, catch panics from their callbacks and pass them to `handler`
+/// instead of unwinding. Passing `None` restores the default unwinding
+/// behavior for effects created inside `f`.
+pub fn with_error_handler<T>(handler: Option<ErrorHandler>, f: impl FnOnce() -> T) -> T {
+    let _guard = ErrorHandlerGuard::set(handler);
+    f()
+}
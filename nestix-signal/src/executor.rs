@@ -0,0 +1,115 @@
+use std::{
+    cell::{Cell, RefCell},
+    collections::HashMap,
+    pin::Pin,
+    rc::Rc,
+    sync::{Arc, Mutex},
+    task::{Context, Wake, Waker},
+};
+
+thread_local! {
+    static EXECUTOR: RefCell<Option<Rc<dyn Executor>>> = const { RefCell::new(None) };
+}
+
+/// A future spawned by the signal runtime.
+pub type LocalFuture = Pin<Box<dyn Future<Output = ()>>>;
+
+/// Runs the futures that drive resources.
+///
+/// Implement this for the async runtime of the host application, and install
+/// it with [`set_executor`].
+pub trait Executor {
+    /// Spawns `future` to run to completion on the current thread.
+    fn spawn_local(&self, future: LocalFuture);
+}
+
+/// Sets the executor used to spawn resource fetches on the current thread.
+pub fn set_executor(executor: impl Executor + 'static) {
+    EXECUTOR.with(|current| current.replace(Some(Rc::new(executor))));
+    #[cfg(feature = "sync")]
+    crate::sync::respawn_inbox_task();
+}
+
+pub(crate) fn spawn_local(future: LocalFuture) {
+    let executor = EXECUTOR.with_borrow(|executor| executor.clone());
+    let executor = executor.expect("no executor has been set for this thread");
+    executor.spawn_local(future);
+}
+
+/// A minimal single-threaded executor that runs tasks on demand.
+///
+/// Spawned tasks do not run until [`LocalExecutor::run_until_stalled`] is
+/// called, which makes the executor convenient for tests. Clones share the
+/// same task queue.
+#[derive(Clone, Default)]
+pub struct LocalExecutor {
+    inner: Rc<LocalExecutorInner>,
+}
+
+#[derive(Default)]
+struct LocalExecutorInner {
+    tasks: RefCell<HashMap<usize, LocalFuture>>,
+    next_id: Cell<usize>,
+    woken: Arc<Mutex<Vec<usize>>>,
+}
+
+struct TaskWaker {
+    id: usize,
+    woken: Arc<Mutex<Vec<usize>>>,
+}
+
+impl Wake for TaskWaker {
+    fn wake(self: Arc<Self>) {
+        self.woken.lock().unwrap().push(self.id);
+    }
+}
+
+impl LocalExecutor {
+    /// Creates an executor with no tasks.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Polls woken tasks until none of them can make further progress.
+    pub fn run_until_stalled(&self) {
+        loop {
+            let woken = std::mem::take(&mut *self.inner.woken.lock().unwrap());
+            if woken.is_empty() {
+                break;
+            }
+
+            for id in woken {
+                // The task is taken out while polling so that it can spawn
+                // further tasks.
+                let Some(mut task) = self.inner.tasks.borrow_mut().remove(&id) else {
+                    continue;
+                };
+                let waker = Waker::from(Arc::new(TaskWaker {
+                    id,
+                    woken: self.inner.woken.clone(),
+                }));
+                if task
+                    .as_mut()
+                    .poll(&mut Context::from_waker(&waker))
+                    .is_pending()
+                {
+                    self.inner.tasks.borrow_mut().insert(id, task);
+                }
+            }
+        }
+    }
+
+    /// Returns the number of spawned tasks that have not completed.
+    pub fn pending_tasks(&self) -> usize {
+        self.inner.tasks.borrow().len()
+    }
+}
+
+impl Executor for LocalExecutor {
+    fn spawn_local(&self, future: LocalFuture) {
+        let id = self.inner.next_id.get();
+        self.inner.next_id.set(id + 1);
+        self.inner.tasks.borrow_mut().insert(id, future);
+        self.inner.woken.lock().unwrap().push(id);
+    }
+}
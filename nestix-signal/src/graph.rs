@@ -0,0 +1,268 @@
+use std::{
+    cell::{Cell, RefCell},
+    collections::{HashMap, HashSet},
+    fmt::Write,
+    panic::Location,
+};
+
+use crate::{DependencySet, Effect, WeakShared, shared::Shared};
+
+thread_local! {
+    static GRAPH_NODES: RefCell<Vec<NodeEntry>> = const { RefCell::new(Vec::new()) };
+    static NEXT_NODE_ID: Cell<usize> = const { Cell::new(0) };
+    static PRUNE_THRESHOLD: Cell<usize> = const { Cell::new(64) };
+}
+
+/// The kind of a node in a [`SignalGraph`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum GraphNodeKind {
+    /// A [`State`](crate::State).
+    State,
+    /// A [`Computed`](crate::Computed) value.
+    Computed,
+    /// An effect registered with [`effect`](crate::effect).
+    Effect,
+}
+
+impl GraphNodeKind {
+    fn name(self) -> &'static str {
+        match self {
+            Self::State => "state",
+            Self::Computed => "computed",
+            Self::Effect => "effect",
+        }
+    }
+}
+
+/// A live state, computed value, or effect.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GraphNode {
+    /// An identifier that is unique on the current thread.
+    pub id: usize,
+    /// What kind of signal this node is.
+    pub kind: GraphNodeKind,
+    /// Where the node was created.
+    pub location: &'static Location<'static>,
+}
+
+/// A dependency from the node `from` to the node `to`, which reads it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct GraphEdge {
+    /// The id of the node that is read.
+    pub from: usize,
+    /// The id of the computed value or effect that reads it.
+    pub to: usize,
+}
+
+/// A snapshot of the reactive dependency graph of the current thread.
+///
+/// Created with [`signal_graph`].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SignalGraph {
+    /// Live nodes, ordered by id.
+    pub nodes: Vec<GraphNode>,
+    /// Dependencies between live nodes, ordered by source and then target.
+    pub edges: Vec<GraphEdge>,
+}
+
+impl SignalGraph {
+    /// Returns the first node created on `line` of `file`, if any.
+    pub fn node_at(&self, file: &str, line: u32) -> Option<&GraphNode> {
+        self.nodes
+            .iter()
+            .find(|node| node.location.file() == file && node.location.line() == line)
+    }
+
+    /// Returns the ids of the nodes that the node `id` reads.
+    pub fn sources(&self, id: usize) -> Vec<usize> {
+        self.edges
+            .iter()
+            .filter(|edge| edge.to == id)
+            .map(|edge| edge.from)
+            .collect()
+    }
+
+    /// Returns the ids of the nodes that read the node `id`.
+    pub fn dependents(&self, id: usize) -> Vec<usize> {
+        self.edges
+            .iter()
+            .filter(|edge| edge.from == id)
+            .map(|edge| edge.to)
+            .collect()
+    }
+
+    /// Renders the graph in Graphviz DOT format.
+    pub fn to_dot(&self) -> String {
+        let mut dot = String::from("digraph signals {\n");
+        for node in &self.nodes {
+            let shape = match node.kind {
+                GraphNodeKind::State => "ellipse",
+                GraphNodeKind::Computed => "box",
+                GraphNodeKind::Effect => "hexagon",
+            };
+            let label = format!("{} {}", node.kind.name(), node.location);
+            writeln!(
+                dot,
+                "    n{} [label=\"{}\", shape={shape}];",
+                node.id,
+                escape_string(&label)
+            )
+            .unwrap();
+        }
+        for edge in &self.edges {
+            writeln!(dot, "    n{} -> n{};", edge.from, edge.to).unwrap();
+        }
+        dot.push_str("}\n");
+        dot
+    }
+
+    /// Renders the graph as a JSON object with `nodes` and `edges` arrays.
+    pub fn to_json(&self) -> String {
+        let nodes = self
+            .nodes
+            .iter()
+            .map(|node| {
+                format!(
+                    "{{\"id\":{},\"kind\":\"{}\",\"file\":\"{}\",\"line\":{},\"column\":{}}}",
+                    node.id,
+                    node.kind.name(),
+                    escape_string(node.location.file()),
+                    node.location.line(),
+                    node.location.column()
+                )
+            })
+            .collect::<Vec<_>>();
+        let edges = self
+            .edges
+            .iter()
+            .map(|edge| format!("{{\"from\":{},\"to\":{}}}", edge.from, edge.to))
+            .collect::<Vec<_>>();
+        format!(
+            "{{\"nodes\":[{}],\"edges\":[{}]}}",
+            nodes.join(","),
+            edges.join(",")
+        )
+    }
+}
+
+/// Escapes `value` for a double-quoted DOT or JSON string.
+fn escape_string(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len());
+    for char in value.chars() {
+        match char {
+            '"' => escaped.push_str("\\\""),
+            '\\' => escaped.push_str("\\\\"),
+            '\n' => escaped.push_str("\\n"),
+            char if char.is_control() => write!(escaped, "\\u{:04x}", char as u32).unwrap(),
+            char => escaped.push(char),
+        }
+    }
+    escaped
+}
+
+struct NodeEntry {
+    node: GraphNode,
+    /// The set of effects notified when this node changes.
+    dependents: Option<WeakShared<RefCell<HashSet<Shared<Effect>>>>>,
+    /// The effect whose dependencies are the sources of this node.
+    reader: Option<WeakShared<Effect>>,
+}
+
+impl NodeEntry {
+    fn is_live(&self) -> bool {
+        match &self.reader {
+            Some(reader) => reader
+                .upgrade()
+                .is_some_and(|reader| !reader.is_cancelled()),
+            None => self
+                .dependents
+                .as_ref()
+                .is_some_and(|dependents| dependents.upgrade().is_some()),
+        }
+    }
+}
+
+/// Records a node when graph tracking is enabled.
+pub(crate) fn register_node(
+    kind: GraphNodeKind,
+    location: &'static Location<'static>,
+    dependents: Option<&DependencySet>,
+    reader: Option<&Shared<Effect>>,
+) {
+    #[cfg(debug_assertions)]
+    if crate::get_config().track_graph {
+        let id = NEXT_NODE_ID.with(|id| id.replace(id.get() + 1));
+        let entry = NodeEntry {
+            node: GraphNode { id, kind, location },
+            dependents: dependents.map(Shared::downgrade),
+            reader: reader.map(Shared::downgrade),
+        };
+        GRAPH_NODES.with_borrow_mut(|nodes| {
+            nodes.push(entry);
+            // Prune dead nodes once the list has doubled since the last prune.
+            let threshold = PRUNE_THRESHOLD.get();
+            if nodes.len() >= threshold {
+                nodes.retain(NodeEntry::is_live);
+                PRUNE_THRESHOLD.set((nodes.len() * 2).max(64));
+            }
+        });
+    }
+    #[cfg(not(debug_assertions))]
+    let _ = (kind, location, dependents, reader);
+}
+
+/// Returns a snapshot of the live states, computed values, and effects on the
+/// current thread and the dependencies between them.
+///
+/// Nodes are only recorded while
+/// [`DebugConfig::track_graph`](crate::DebugConfig::track_graph) is enabled,
+/// so enable it with [`debug_signals`](crate::debug_signals) before creating
+/// the signals to inspect. In release builds the graph is always empty.
+///
+/// ```
+/// use nestix_signal::{DebugConfig, computed, create_state, debug_signals, signal_graph};
+///
+/// debug_signals(DebugConfig {
+///     track_graph: true,
+///     ..Default::default()
+/// });
+///
+/// let count = create_state(1);
+/// let doubled = computed(move || count.get() * 2);
+/// doubled.get();
+///
+/// println!("{}", signal_graph().to_dot());
+/// ```
+pub fn signal_graph() -> SignalGraph {
+    GRAPH_NODES.with_borrow_mut(|entries| {
+        entries.retain(NodeEntry::is_live);
+
+        let mut sources = HashMap::new();
+        for entry in entries.iter() {
+            if let Some(dependents) = entry.dependents.as_ref().and_then(WeakShared::upgrade) {
+                sources.insert(dependents, entry.node.id);
+            }
+        }
+
+        let mut edges = Vec::new();
+        for entry in entries.iter() {
+            let Some(reader) = entry.reader.as_ref().and_then(WeakShared::upgrade) else {
+                continue;
+            };
+            for dependency_set in reader.dependency_sets() {
+                if let Some(&from) = sources.get(&dependency_set) {
+                    edges.push(GraphEdge {
+                        from,
+                        to: entry.node.id,
+                    });
+                }
+            }
+        }
+        edges.sort();
+
+        SignalGraph {
+            nodes: entries.iter().map(|entry| entry.node.clone()).collect(),
+            edges,
+        }
+    })
+}
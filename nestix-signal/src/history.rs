@@ -0,0 +1,251 @@
+use std::{
+    cell::{Cell, Ref, RefCell},
+    collections::VecDeque,
+    rc::Rc,
+};
+
+use crate::{Signal, State, batch, create_state, current_batch};
+
+struct HistoryData<T> {
+    state: State<T>,
+    past: RefCell<VecDeque<T>>,
+    future: RefCell<Vec<T>>,
+    limit: usize,
+    /// The batch that recorded the latest history entry.
+    last_batch: Cell<Option<u64>>,
+    can_undo: State<bool>,
+    can_redo: State<bool>,
+}
+
+/// A mutable reactive value that remembers its previous values.
+///
+/// Every write records the value it replaces, so it can be restored with
+/// [`HistoryState::undo`]. All writes made inside one [`batch`] form a single
+/// history entry. At most `limit` entries are kept; the oldest are dropped
+/// first.
+///
+/// ```
+/// use nestix_signal::{batch, create_history_state};
+///
+/// let text = create_history_state(String::new(), 100);
+/// text.set("a".to_string());
+/// batch(|| {
+///     text.set("ab".to_string());
+///     text.set("abc".to_string());
+/// });
+///
+/// text.undo();
+/// assert_eq!(text.get(), "a");
+/// text.redo();
+/// assert_eq!(text.get(), "abc");
+/// ```
+pub struct HistoryState<T> {
+    data: Rc<HistoryData<T>>,
+}
+
+impl<T> HistoryState<T> {
+    /// Borrows the current value and records a dependency if tracking is active.
+    pub fn borrow(&'_ self) -> Ref<'_, T> {
+        self.data.state.borrow()
+    }
+
+    /// Returns whether [`HistoryState::undo`] would restore a value.
+    ///
+    /// Inside an effect or computed value, the caller reruns when this changes.
+    pub fn can_undo(&self) -> bool {
+        self.data.can_undo.get()
+    }
+
+    /// Returns whether [`HistoryState::redo`] would restore a value.
+    ///
+    /// Inside an effect or computed value, the caller reruns when this changes.
+    pub fn can_redo(&self) -> bool {
+        self.data.can_redo.get()
+    }
+
+    /// Returns the underlying state.
+    ///
+    /// Writes made directly to the returned state are not recorded.
+    pub fn state(&self) -> State<T> {
+        self.data.state.clone()
+    }
+
+    /// Replaces the current value and always notifies dependents.
+    #[track_caller]
+    pub fn set_unchecked(&self, value: T)
+    where
+        T: Clone,
+    {
+        self.record();
+        self.data.state.set_unchecked(value);
+    }
+
+    /// Replaces the current value with the result of `updater`.
+    #[track_caller]
+    pub fn update(&self, updater: impl FnOnce(&T) -> T)
+    where
+        T: Clone,
+    {
+        self.record();
+        self.data.state.update(updater);
+    }
+
+    /// Mutates the current value in place and then notifies dependents.
+    #[track_caller]
+    pub fn mutate(&self, mutator: impl FnOnce(&mut T))
+    where
+        T: Clone,
+    {
+        self.record();
+        self.data.state.mutate(mutator);
+    }
+
+    /// Restores the value before the latest history entry.
+    ///
+    /// Returns `false` if there is nothing to undo.
+    #[track_caller]
+    pub fn undo(&self) -> bool {
+        let Some(value) = self.data.past.borrow_mut().pop_back() else {
+            return false;
+        };
+        let current = self.data.state.replace(value);
+        self.data.future.borrow_mut().push(current);
+        self.finish_travel();
+        true
+    }
+
+    /// Restores the value replaced by the latest [`HistoryState::undo`].
+    ///
+    /// Returns `false` if there is nothing to redo.
+    #[track_caller]
+    pub fn redo(&self) -> bool {
+        let Some(value) = self.data.future.borrow_mut().pop() else {
+            return false;
+        };
+        let current = self.data.state.replace(value);
+        self.data.past.borrow_mut().push_back(current);
+        self.finish_travel();
+        true
+    }
+
+    /// Forgets all recorded history, keeping the current value.
+    #[track_caller]
+    pub fn clear_history(&self) {
+        self.data.past.borrow_mut().clear();
+        self.data.future.borrow_mut().clear();
+        self.data.last_batch.set(None);
+        self.sync_flags();
+    }
+
+    /// Records the current value as a new history entry unless one was
+    /// already recorded in the current batch.
+    #[track_caller]
+    fn record(&self)
+    where
+        T: Clone,
+    {
+        let batch = current_batch();
+        if batch.is_some() && batch == self.data.last_batch.get() {
+            return;
+        }
+        self.data.last_batch.set(batch);
+
+        let current = self.data.state.borrow_silently().clone();
+        let mut past = self.data.past.borrow_mut();
+        past.push_back(current);
+        while past.len() > self.data.limit {
+            past.pop_front();
+        }
+        drop(past);
+        self.data.future.borrow_mut().clear();
+        self.sync_flags();
+    }
+
+    #[track_caller]
+    fn finish_travel(&self) {
+        // A write after undo or redo in the same batch starts a new entry.
+        self.data.last_batch.set(None);
+        self.sync_flags();
+    }
+
+    #[track_caller]
+    fn sync_flags(&self) {
+        let can_undo = !self.data.past.borrow().is_empty();
+        let can_redo = !self.data.future.borrow().is_empty();
+        batch(|| {
+            self.data.can_undo.set(can_undo);
+            self.data.can_redo.set(can_redo);
+        });
+    }
+}
+
+impl<T: Clone + PartialEq> HistoryState<T> {
+    /// Replaces the current value if it changed, recording the previous value.
+    #[track_caller]
+    pub fn set(&self, value: T) {
+        if *self.data.state.borrow_silently() == value {
+            return;
+        }
+        self.record();
+        self.data.state.set_unchecked(value);
+    }
+}
+
+impl<T: Clone> HistoryState<T> {
+    /// Clones and returns the current value, recording a dependency if tracking
+    /// is active.
+    pub fn get(&self) -> T {
+        self.data.state.get()
+    }
+}
+
+impl<T: Clone + 'static> Signal for HistoryState<T> {
+    type Output = T;
+
+    fn get(&self) -> T {
+        self.get()
+    }
+
+    fn box_clone(&self) -> Box<dyn Signal<Output = T>> {
+        Box::new(self.clone())
+    }
+}
+
+impl<T> Clone for HistoryState<T> {
+    fn clone(&self) -> Self {
+        Self {
+            data: self.data.clone(),
+        }
+    }
+}
+
+impl<T> PartialEq for HistoryState<T> {
+    fn eq(&self, other: &Self) -> bool {
+        Rc::ptr_eq(&self.data, &other.data)
+    }
+}
+
+impl<T> State<T> {
+    /// Wraps this state in a [`HistoryState`] that keeps up to `limit` undo
+    /// entries.
+    #[track_caller]
+    pub fn with_history(self, limit: usize) -> HistoryState<T> {
+        HistoryState {
+            data: Rc::new(HistoryData {
+                state: self,
+                past: RefCell::new(VecDeque::new()),
+                future: RefCell::new(Vec::new()),
+                limit,
+                last_batch: Cell::new(None),
+                can_undo: create_state(false),
+                can_redo: create_state(false),
+            }),
+        }
+    }
+}
+
+/// Creates a reactive value that keeps up to `limit` undo entries.
+#[track_caller]
+pub fn create_history_state<T>(value: T, limit: usize) -> HistoryState<T> {
+    create_state(value).with_history(limit)
+}
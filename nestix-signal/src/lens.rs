@@ -0,0 +1,244 @@
+use std::{cell::OnceCell, fmt::Debug, rc::Rc};
+
+use crate::{
+    Computed, Readonly, ScopeGuard, Signal, State, create_state, current_effect, effect, memo,
+    untrack,
+};
+
+/// A writable signal onto a part of another state.
+///
+/// Reading a lens records a dependency like reading a [`State`] does, but
+/// readers are only notified when the part the lens projects changes. Writing
+/// through a lens updates the parent state.
+///
+/// ```
+/// use nestix_signal::create_state;
+///
+/// #[derive(Clone)]
+/// struct Form {
+///     name: String,
+///     email: String,
+/// }
+///
+/// let form = create_state(Form {
+///     name: String::new(),
+///     email: String::new(),
+/// });
+/// let name = form.lens(|form| form.name.clone(), |form, name| form.name = name);
+///
+/// name.set("Alice".to_string());
+/// assert_eq!(form.get().name, "Alice");
+/// ```
+pub struct Lens<T> {
+    read: Rc<dyn Fn() -> T>,
+    /// Stops notifications when the projected part is unchanged. `None` when
+    /// `read` already has that cutoff, and empty until the first tracked read.
+    cutoff: Option<Rc<OnceCell<Computed<T>>>>,
+    write: Rc<dyn Fn(T)>,
+}
+
+impl<T: Clone + PartialEq + 'static> Lens<T> {
+    fn new(read: impl Fn() -> T + 'static, write: impl Fn(T) + 'static) -> Self {
+        Self {
+            read: Rc::new(read),
+            cutoff: Some(Rc::new(OnceCell::new())),
+            write: Rc::new(write),
+        }
+    }
+
+    /// Creates a lens whose `read` only notifies when its value changes.
+    fn without_cutoff(read: impl Fn() -> T + 'static, write: impl Fn(T) + 'static) -> Self {
+        Self {
+            read: Rc::new(read),
+            cutoff: None,
+            write: Rc::new(write),
+        }
+    }
+
+    /// Returns the current value, recording a dependency if tracking is active.
+    pub fn get(&self) -> T {
+        let Some(cutoff) = &self.cutoff else {
+            return (self.read)();
+        };
+        if let Some(value) = cutoff.get() {
+            return value.get();
+        }
+        if current_effect().is_none() {
+            return (self.read)();
+        }
+        cutoff
+            .get_or_init(|| {
+                // The memo is shared by every clone of this lens, so it must
+                // not belong to whichever effect happens to read first.
+                let _scope = ScopeGuard::set(None);
+                let read = self.read.clone();
+                untrack(|| memo(move || read()))
+            })
+            .get()
+    }
+
+    /// Writes `value` to the parent state if it differs from the current value.
+    pub fn set(&self, value: T) {
+        if untrack(|| self.get()) != value {
+            (self.write)(value);
+        }
+    }
+
+    /// Replaces the current value with the result of `updater`.
+    pub fn update(&self, updater: impl FnOnce(&T) -> T) {
+        let value = untrack(|| self.get());
+        self.set(updater(&value));
+    }
+
+    /// Creates a lens onto a part of this lens's value.
+    ///
+    /// Writes clone the current value, apply `set`, and write the result back.
+    pub fn lens<U: Clone + PartialEq + 'static>(
+        &self,
+        get: impl Fn(&T) -> U + 'static,
+        set: impl Fn(&mut T, U) + 'static,
+    ) -> Lens<U> {
+        let parent = self.clone();
+        let reader = self.clone();
+        Lens::new(
+            move || get(&reader.get()),
+            move |value| {
+                parent.update(|prev| {
+                    let mut next = prev.clone();
+                    set(&mut next, value);
+                    next
+                })
+            },
+        )
+    }
+
+    /// Converts this lens into a read-only signal handle.
+    pub fn into_readonly(self) -> Readonly<T> {
+        Readonly::new(self)
+    }
+
+    /// Converts this lens into a state that stays in sync with it.
+    ///
+    /// Writes to either one reach the other when effects run. The effects
+    /// that keep them in sync belong to the current [`Scope`](crate::Scope),
+    /// so the state stops following the lens when that scope is disposed.
+    pub fn into_state(self) -> State<T> {
+        let state = create_state(untrack(|| self.get()));
+        effect({
+            let lens = self.clone();
+            let state = state.clone();
+            move || {
+                let value = lens.get();
+                untrack(|| state.set(value));
+            }
+        });
+        effect({
+            let state = state.clone();
+            move || {
+                let value = state.get();
+                untrack(|| self.set(value));
+            }
+        });
+        state
+    }
+}
+
+/// Lets a lens be passed wherever a [`State`] is accepted, such as a two-way
+/// bound component prop. See [`Lens::into_state`].
+impl<T: Clone + PartialEq + 'static> From<Lens<T>> for State<T> {
+    fn from(lens: Lens<T>) -> Self {
+        lens.into_state()
+    }
+}
+
+impl<T> Clone for Lens<T> {
+    fn clone(&self) -> Self {
+        Self {
+            read: self.read.clone(),
+            cutoff: self.cutoff.clone(),
+            write: self.write.clone(),
+        }
+    }
+}
+
+impl<T: Debug + Clone + PartialEq + 'static> Debug for Lens<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_tuple("Lens")
+            .field(&untrack(|| self.get()))
+            .finish()
+    }
+}
+
+impl<T: Clone + PartialEq + 'static> Signal for Lens<T> {
+    type Output = T;
+
+    fn get(&self) -> T {
+        self.get()
+    }
+
+    fn box_clone(&self) -> Box<dyn Signal<Output = T>> {
+        Box::new(self.clone())
+    }
+}
+
+impl<T: 'static> State<T> {
+    /// Creates a writable lens onto a part of this state.
+    ///
+    /// `get` projects the part from the state's value, and `set` writes a new
+    /// part into it. Writes notify every dependent of this state.
+    pub fn lens<U: Clone + PartialEq + 'static>(
+        &self,
+        get: impl Fn(&T) -> U + 'static,
+        set: impl Fn(&mut T, U) + 'static,
+    ) -> Lens<U> {
+        let reader = self.clone();
+        let writer = self.clone();
+        Lens::new(
+            move || get(&reader.borrow()),
+            move |value| writer.mutate(|state| set(state, value)),
+        )
+    }
+
+    /// Creates a writable lens onto the part of this state that `f` borrows.
+    ///
+    /// Since `f` needs a mutable reference, reads apply it to a clone of the
+    /// state's value. Prefer [`State::lens`] for large values.
+    ///
+    /// ```
+    /// use nestix_signal::create_state;
+    ///
+    /// let point = create_state((1, 2));
+    /// let x = point.map_mut(|point| &mut point.0);
+    ///
+    /// x.update(|x| x + 10);
+    /// assert_eq!(point.get(), (11, 2));
+    /// ```
+    pub fn map_mut<U: Clone + PartialEq + 'static>(
+        &self,
+        f: impl Fn(&mut T) -> &mut U + 'static,
+    ) -> Lens<U>
+    where
+        T: Clone,
+    {
+        let f = Rc::new(f);
+        let reader = self.clone();
+        let writer = self.clone();
+        Lens::new(
+            {
+                let f = f.clone();
+                move || f(&mut reader.get()).clone()
+            },
+            move |value| writer.mutate(|state| *f(state) = value),
+        )
+    }
+}
+
+impl<T: Clone + PartialEq + 'static> State<T> {
+    /// Converts this state into a lens onto its whole value.
+    ///
+    /// This lets components that accept a [`Lens`] be bound to a plain state.
+    pub fn into_lens(self) -> Lens<T> {
+        let writer = self.clone();
+        Lens::without_cutoff(move || self.get(), move |value| writer.set(value))
+    }
+}
@@ -2,20 +2,58 @@
 //! signal wrappers. Reading a signal inside an effect or computed value records
 //! a dependency, and writes notify the dependent computations.
 
+mod cleanup;
 mod computed;
 mod config;
 mod effect;
+mod error;
+mod executor;
+mod graph;
+mod history;
+mod lens;
+mod list_diff;
 mod readonly;
+mod resource;
+mod scheduler;
+mod scope;
 mod shared;
 mod signal;
+#[cfg(feature = "serde")]
+mod snapshot;
 mod state;
+mod state_map;
+mod state_vec;
+mod store;
+mod suspense;
+#[cfg(feature = "sync")]
+mod sync;
+mod timer;
 mod untrack;
 
+pub use cleanup::*;
 pub use computed::*;
 pub use config::*;
 pub use effect::*;
+pub use error::*;
+pub use executor::*;
+pub use graph::*;
+pub use history::*;
+pub use lens::*;
+pub use list_diff::{ListDiff, ListDiffs};
 pub use readonly::*;
+pub use resource::*;
+pub use scheduler::*;
+pub use scope::*;
 pub use shared::*;
 pub use signal::*;
+#[cfg(feature = "serde")]
+pub use snapshot::*;
 pub use state::*;
+pub use state_map::*;
+pub use state_vec::*;
+pub use store::*;
+pub use suspense::*;
+#[cfg(feature = "sync")]
+pub use sync::*;
+pub use timer::*;
 pub use untrack::*;
@@ -0,0 +1,125 @@
+use std::{
+    cell::RefCell,
+    rc::{Rc, Weak},
+};
+
+use crate::{State, create_state};
+
+/// A single change to a reactive list.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ListDiff<T> {
+    /// `value` was inserted at `index`.
+    Insert {
+        /// The index of the new item.
+        index: usize,
+        /// The new item.
+        value: T,
+    },
+    /// The item at `index` was removed.
+    Remove {
+        /// The index the item had before it was removed.
+        index: usize,
+    },
+    /// The item at `from` was moved to `to`.
+    ///
+    /// `to` is the index of the item after the move.
+    Move {
+        /// The index of the item before the move.
+        from: usize,
+        /// The index of the item after the move.
+        to: usize,
+    },
+    /// The item at `index` was replaced with `value`.
+    Update {
+        /// The index of the replaced item.
+        index: usize,
+        /// The new item.
+        value: T,
+    },
+    /// The whole list was replaced. Consumers should read it again.
+    Replace,
+}
+
+type DiffQueue<T> = Rc<RefCell<Vec<ListDiff<T>>>>;
+type WeakDiffQueue<T> = Weak<RefCell<Vec<ListDiff<T>>>>;
+
+/// A subscription to the changes of a reactive list.
+///
+/// Obtained from [`Signal::list_diffs`](crate::Signal::list_diffs). Changes
+/// made after the subscription was created are queued until they are taken
+/// with [`ListDiffs::take`]. Cloning a `ListDiffs` produces another handle to
+/// the same queue.
+pub struct ListDiffs<T> {
+    queue: DiffQueue<T>,
+    version: State<u64>,
+}
+
+impl<T> ListDiffs<T> {
+    /// Takes the queued changes in the order they were made.
+    ///
+    /// Inside an effect or computed value, this records a dependency on the
+    /// list, so the caller reruns when further changes are queued.
+    pub fn take(&self) -> Vec<ListDiff<T>> {
+        self.version.get();
+        self.queue.take()
+    }
+}
+
+impl<T> Clone for ListDiffs<T> {
+    fn clone(&self) -> Self {
+        Self {
+            queue: self.queue.clone(),
+            version: self.version.clone(),
+        }
+    }
+}
+
+impl<T> PartialEq for ListDiffs<T> {
+    fn eq(&self, other: &Self) -> bool {
+        Rc::ptr_eq(&self.queue, &other.queue)
+    }
+}
+
+/// Tracks reads of a reactive list and delivers its changes to subscribers.
+pub(crate) struct DiffHub<T> {
+    subscribers: RefCell<Vec<WeakDiffQueue<T>>>,
+    version: State<u64>,
+}
+
+impl<T: Clone> DiffHub<T> {
+    #[track_caller]
+    pub(crate) fn new() -> Self {
+        Self {
+            subscribers: RefCell::new(Vec::new()),
+            version: create_state(0),
+        }
+    }
+
+    /// Records a dependency on the list if tracking is active.
+    pub(crate) fn track(&self) {
+        self.version.get();
+    }
+
+    pub(crate) fn subscribe(&self) -> ListDiffs<T> {
+        let queue = Rc::new(RefCell::new(Vec::new()));
+        self.subscribers.borrow_mut().push(Rc::downgrade(&queue));
+        ListDiffs {
+            queue,
+            version: self.version.clone(),
+        }
+    }
+
+    /// Queues `diffs` for every live subscriber and notifies dependents.
+    #[track_caller]
+    pub(crate) fn emit(&self, diffs: impl IntoIterator<Item = ListDiff<T>>) {
+        let diffs = diffs.into_iter().collect::<Vec<_>>();
+        self.subscribers.borrow_mut().retain(|subscriber| {
+            let Some(queue) = subscriber.upgrade() else {
+                return false;
+            };
+            queue.borrow_mut().extend(diffs.iter().cloned());
+            true
+        });
+        self.version.update(|version| version + 1);
+    }
+}
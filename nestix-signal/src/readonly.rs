@@ -1,4 +1,4 @@
-use crate::Signal;
+use crate::{ListDiffs, Signal};
 
 /// A cloneable, read-only wrapper around any signal.
 ///
@@ -34,6 +34,13 @@ impl<T: 'static + Clone> Signal for Readonly<T> {
     fn box_clone(&self) -> Box<dyn Signal<Output = T>> {
         Box::new(self.clone())
     }
+
+    fn list_diffs(&self) -> Option<ListDiffs<<T as IntoIterator>::Item>>
+    where
+        T: IntoIterator,
+    {
+        self.signal.list_diffs()
+    }
 }
 
 impl<T> Clone for Readonly<T> {
@@ -0,0 +1,162 @@
+use std::{cell::Cell, rc::Rc};
+
+use crate::{
+    Signal, State, create_state, current_suspense, effect, executor::spawn_local, untrack,
+};
+
+/// The loading state of a [`Resource`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ResourceState<T, E> {
+    /// The latest fetch has not completed yet.
+    Loading,
+    /// The latest fetch completed successfully.
+    Ready(T),
+    /// The latest fetch failed.
+    Error(E),
+}
+
+impl<T, E> From<Result<T, E>> for ResourceState<T, E> {
+    fn from(result: Result<T, E>) -> Self {
+        match result {
+            Ok(value) => Self::Ready(value),
+            Err(error) => Self::Error(error),
+        }
+    }
+}
+
+/// A value loaded asynchronously from a source signal.
+///
+/// Created with [`create_resource`]. Cloning a `Resource` produces another
+/// handle to the same loading state.
+pub struct Resource<T, E> {
+    state: State<ResourceState<T, E>>,
+    is_loading: Rc<dyn Fn() -> bool>,
+    refetch: Rc<dyn Fn()>,
+}
+
+impl<T: Clone, E: Clone> Resource<T, E> {
+    /// Returns the loaded value, or `None` while loading or after an error.
+    ///
+    /// The read is reported to the current suspense tracker, if any, so an
+    /// enclosing suspense boundary waits for this resource.
+    pub fn get(&self) -> Option<T> {
+        if let Some(tracker) = current_suspense() {
+            tracker.register(&self.is_loading);
+        }
+
+        match &*self.state.borrow() {
+            ResourceState::Ready(value) => Some(value.clone()),
+            _ => None,
+        }
+    }
+
+    /// Returns the current loading state.
+    pub fn state(&self) -> ResourceState<T, E> {
+        self.state.get()
+    }
+
+    /// Returns whether the latest fetch has not completed yet.
+    pub fn loading(&self) -> bool {
+        (self.is_loading)()
+    }
+
+    /// Returns the error of the latest fetch, if it failed.
+    pub fn error(&self) -> Option<E> {
+        match &*self.state.borrow() {
+            ResourceState::Error(error) => Some(error.clone()),
+            _ => None,
+        }
+    }
+
+    /// Fetches again with the current source value.
+    pub fn refetch(&self) {
+        (self.refetch)();
+    }
+}
+
+impl<T, E> Clone for Resource<T, E> {
+    fn clone(&self) -> Self {
+        Self {
+            state: self.state.clone(),
+            is_loading: self.is_loading.clone(),
+            refetch: self.refetch.clone(),
+        }
+    }
+}
+
+impl<T: Clone + 'static, E: Clone + 'static> Signal for Resource<T, E> {
+    type Output = Option<T>;
+
+    fn get(&self) -> Option<T> {
+        self.get()
+    }
+
+    fn box_clone(&self) -> Box<dyn Signal<Output = Option<T>>> {
+        Box::new(self.clone())
+    }
+}
+
+/// Creates a resource that fetches with `fetcher` whenever `source` changes.
+///
+/// The future returned by `fetcher` is spawned on the executor installed with
+/// [`set_executor`](crate::set_executor). The resource is loading until the
+/// latest future completes; results of superseded fetches are discarded.
+///
+/// # Panics
+///
+/// Panics if no executor has been set for the current thread.
+#[track_caller]
+pub fn create_resource<S, T, E, F, Fut>(source: S, fetcher: F) -> Resource<T, E>
+where
+    S: Signal + 'static,
+    T: 'static,
+    E: 'static,
+    F: Fn(S::Output) -> Fut + 'static,
+    Fut: Future<Output = Result<T, E>> + 'static,
+{
+    let state = create_state(ResourceState::Loading);
+    let generation = Rc::new(Cell::new(0_usize));
+
+    let load = Rc::new({
+        let state = state.clone();
+        move |input: S::Output| {
+            let current = generation.get() + 1;
+            generation.set(current);
+            if !matches!(*state.borrow(), ResourceState::Loading) {
+                state.set_unchecked(ResourceState::Loading);
+            }
+
+            let future = fetcher(input);
+            let state = state.clone();
+            let generation = generation.clone();
+            spawn_local(Box::pin(async move {
+                let result = future.await;
+                if generation.get() == current {
+                    state.set_unchecked(result.into());
+                }
+            }));
+        }
+    });
+
+    effect({
+        let source = source.box_clone();
+        let load = load.clone();
+        move || {
+            let input = source.get();
+            untrack(|| load(input));
+        }
+    });
+
+    let is_loading = Rc::new({
+        let state = state.clone();
+        move || matches!(*state.borrow(), ResourceState::Loading)
+    });
+
+    let refetch = Rc::new(move || untrack(|| load(source.get())));
+
+    Resource {
+        state,
+        is_loading,
+        refetch,
+    }
+}
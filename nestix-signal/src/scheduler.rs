@@ -0,0 +1,130 @@
+use std::{
+    cell::{Cell, RefCell},
+    rc::Rc,
+};
+
+use crate::{current_batch, flush_pending_effects, has_pending_effects};
+
+thread_local! {
+    static SCHEDULER: RefCell<Option<Rc<dyn Scheduler>>> = const { RefCell::new(None) };
+    static FLUSH_SCHEDULED: Cell<bool> = const { Cell::new(false) };
+}
+
+/// Decides when pending effects run.
+///
+/// Without a scheduler, effects run as soon as the outermost [`batch`](crate::batch)
+/// completes, and a write outside a batch runs its effects before returning.
+/// With a scheduler installed by [`set_scheduler`], the runtime instead calls
+/// [`Scheduler::schedule`] and leaves the effects pending until the host calls
+/// [`flush_effects`], for example from a microtask or an animation frame. All
+/// writes made before the flush are then handled together.
+pub trait Scheduler {
+    /// Requests a call to [`flush_effects`] at a later point.
+    ///
+    /// This is called once when effects become pending, and not again until
+    /// they have been flushed.
+    fn schedule(&self);
+}
+
+/// Sets the scheduler used to flush effects on the current thread.
+pub fn set_scheduler(scheduler: impl Scheduler + 'static) {
+    SCHEDULER.with(|current| current.replace(Some(Rc::new(scheduler))));
+}
+
+/// Removes the scheduler of the current thread and runs pending effects.
+///
+/// Afterwards, effects run synchronously again.
+pub fn clear_scheduler() {
+    SCHEDULER.with(|current| current.replace(None));
+    flush_effects();
+}
+
+/// Runs all pending effects.
+///
+/// Effects that are notified while the flush runs are handled in the same
+/// flush. Inside a batch, the flush is deferred; the pending effects are
+/// handled, or handed to the scheduler again, when the outermost batch
+/// completes.
+pub fn flush_effects() {
+    FLUSH_SCHEDULED.set(false);
+    if current_batch().is_some() {
+        return;
+    }
+    flush_pending_effects();
+}
+
+/// Hands pending effects to the scheduler of the current thread.
+///
+/// Returns `false` when no scheduler is installed, in which case the caller
+/// flushes synchronously.
+pub(crate) fn schedule_flush() -> bool {
+    let Some(scheduler) = SCHEDULER.with_borrow(|scheduler| scheduler.clone()) else {
+        return false;
+    };
+    if has_pending_effects() && !FLUSH_SCHEDULED.replace(true) {
+        scheduler.schedule();
+    }
+    true
+}
+
+/// A scheduler that flushes only when asked to.
+///
+/// Effects stay pending until [`ManualScheduler::flush`] is called, which
+/// makes the scheduler convenient for tests. Clones share the same state.
+///
+/// ```
+/// use std::{cell::Cell, rc::Rc};
+///
+/// use nestix_signal::{ManualScheduler, create_state, effect, set_scheduler};
+///
+/// let scheduler = ManualScheduler::new();
+/// set_scheduler(scheduler.clone());
+///
+/// let count = create_state(0);
+/// let runs = Rc::new(Cell::new(0));
+/// effect({
+///     let count = count.clone();
+///     let runs = runs.clone();
+///     move || {
+///         count.get();
+///         runs.set(runs.get() + 1);
+///     }
+/// });
+///
+/// count.set(1);
+/// count.set(2);
+/// assert!(scheduler.is_scheduled());
+/// assert_eq!(runs.get(), 1);
+///
+/// scheduler.flush();
+/// assert_eq!(runs.get(), 2);
+/// ```
+#[derive(Clone, Default)]
+pub struct ManualScheduler {
+    scheduled: Rc<Cell<bool>>,
+}
+
+impl ManualScheduler {
+    /// Creates a scheduler with no flush requested.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns whether a flush was requested since the last
+    /// [`ManualScheduler::flush`].
+    pub fn is_scheduled(&self) -> bool {
+        self.scheduled.get()
+    }
+
+    /// Runs all pending effects.
+    pub fn flush(&self) {
+        self.scheduled.set(false);
+        flush_effects();
+    }
+}
+
+impl Scheduler for ManualScheduler {
+    fn schedule(&self) {
+        self.scheduled.set(true);
+    }
+}
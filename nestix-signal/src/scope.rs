@@ -0,0 +1,214 @@
+use std::{
+    cell::{Cell, RefCell},
+    fmt::Debug,
+    rc::{Rc, Weak},
+};
+
+use crate::{
+    Cleanup, CleanupOwner, CleanupOwnerGuard, Effect, EffectHandle, effect, run_cleanups,
+    shared::Shared,
+};
+
+thread_local! {
+    static CURRENT_SCOPE: RefCell<Option<Scope>> = const { RefCell::new(None) };
+}
+
+/// An owner of effects, computed values, and nested scopes.
+///
+/// Effects and computed values created while a scope is current belong to it,
+/// and so do scopes created with [`Scope::new`]. Disposing a scope cancels
+/// everything it owns, nested scopes first. Each run of an effect gets its own
+/// nested scope, so effects and computed values created while it runs are
+/// disposed before it reruns and when it is canceled. Likewise, what a
+/// computed value creates is disposed before it is evaluated again.
+///
+/// A scope keeps the effects it owns alive until they are canceled or the
+/// scope is disposed. Cloning a `Scope` produces another handle to the same
+/// scope.
+#[derive(Clone)]
+pub struct Scope {
+    data: Rc<ScopeData>,
+}
+
+struct ScopeData {
+    parent: Weak<ScopeData>,
+    children: RefCell<Vec<Scope>>,
+    effects: RefCell<Vec<Shared<Effect>>>,
+    cleanups: RefCell<Vec<Cleanup>>,
+    disposed: Cell<bool>,
+}
+
+impl Scope {
+    /// Creates a scope owned by the current scope, if there is one.
+    pub fn new() -> Self {
+        Self::new_in(current_scope())
+    }
+
+    /// Creates a scope owned by this scope.
+    pub fn child(&self) -> Self {
+        Self::new_in(Some(self.clone()))
+    }
+
+    pub(crate) fn new_in(parent: Option<Scope>) -> Self {
+        let scope = Self::with_parent(parent.as_ref());
+        if let Some(parent) = parent {
+            if parent.is_disposed() {
+                scope.data.disposed.set(true);
+            } else {
+                parent.data.children.borrow_mut().push(scope.clone());
+            }
+        }
+        scope
+    }
+
+    fn with_parent(parent: Option<&Scope>) -> Self {
+        Self {
+            data: Rc::new(ScopeData {
+                parent: parent.map_or_else(Weak::new, |parent| Rc::downgrade(&parent.data)),
+                children: RefCell::new(Vec::new()),
+                effects: RefCell::new(Vec::new()),
+                cleanups: RefCell::new(Vec::new()),
+                disposed: Cell::new(false),
+            }),
+        }
+    }
+
+    /// Runs `f` with this scope as the current scope.
+    ///
+    /// Cleanups registered with [`on_cleanup`](crate::on_cleanup) directly
+    /// inside `f` run when this scope is disposed.
+    pub fn run<T>(&self, f: impl FnOnce() -> T) -> T {
+        let _scope = ScopeGuard::set(Some(self.clone()));
+        let _cleanup_owner = CleanupOwnerGuard::set(Some(CleanupOwner::Scope(self.clone())));
+        f()
+    }
+
+    /// Registers an effect owned by this scope and runs it immediately.
+    ///
+    /// This is equivalent to calling [`effect`] inside [`Scope::run`].
+    #[track_caller]
+    pub fn effect(&self, f: impl Fn() + 'static) -> EffectHandle {
+        let _guard = ScopeGuard::set(Some(self.clone()));
+        effect(f)
+    }
+
+    /// Cancels every effect and computed value owned by this scope, disposes
+    /// its nested scopes, and then runs its cleanups.
+    ///
+    /// Effects created in a disposed scope are canceled immediately. Calling
+    /// `dispose` more than once is harmless.
+    pub fn dispose(&self) {
+        if self.data.disposed.replace(true) {
+            return;
+        }
+
+        for child in self.data.children.take() {
+            child.dispose();
+        }
+        for effect in self.data.effects.take() {
+            Effect::cancel(&effect);
+        }
+        run_cleanups(self.data.cleanups.take());
+
+        if let Some(parent) = self.data.parent.upgrade() {
+            parent
+                .children
+                .borrow_mut()
+                .retain(|child| !Rc::ptr_eq(&child.data, &self.data));
+        }
+    }
+
+    /// Returns whether this scope has been disposed.
+    pub fn is_disposed(&self) -> bool {
+        self.data.disposed.get()
+    }
+
+    pub(crate) fn add_cleanup(&self, cleanup: Cleanup) {
+        if self.is_disposed() {
+            run_cleanups(vec![cleanup]);
+        } else {
+            self.data.cleanups.borrow_mut().push(cleanup);
+        }
+    }
+
+    pub(crate) fn own_effect(&self, effect: &Shared<Effect>) {
+        if self.is_disposed() {
+            Effect::cancel(effect);
+        } else {
+            self.data.effects.borrow_mut().push(effect.clone());
+        }
+    }
+
+    pub(crate) fn disown_effect(&self, effect: &Shared<Effect>) {
+        self.data
+            .effects
+            .borrow_mut()
+            .retain(|owned| owned != effect);
+    }
+}
+
+impl Default for Scope {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Debug for Scope {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Scope")
+            .field("disposed", &self.is_disposed())
+            .finish_non_exhaustive()
+    }
+}
+
+impl PartialEq for Scope {
+    fn eq(&self, other: &Self) -> bool {
+        Rc::ptr_eq(&self.data, &other.data)
+    }
+}
+
+impl Eq for Scope {}
+
+/// Returns the scope that owns effects created now, if any.
+pub fn current_scope() -> Option<Scope> {
+    CURRENT_SCOPE.with_borrow(|scope| scope.clone())
+}
+
+pub(crate) struct ScopeGuard(Option<Scope>);
+
+impl ScopeGuard {
+    pub(crate) fn set(scope: Option<Scope>) -> Self {
+        Self(CURRENT_SCOPE.replace(scope))
+    }
+}
+
+impl Drop for ScopeGuard {
+    fn drop(&mut self) {
+        CURRENT_SCOPE.replace(self.0.take());
+    }
+}
+
+/// Runs `f` in a new root scope and returns its result.
+///
+/// The root scope is not owned by the current scope, so it lives until it is
+/// disposed through the [`Scope`] passed to `f`.
+///
+/// ```
+/// use nestix_signal::{create_root, create_state, effect};
+///
+/// let count = create_state(0);
+/// let scope = create_root(|scope| {
+///     effect({
+///         let count = count.clone();
+///         move || println!("count: {}", count.get())
+///     });
+///     scope
+/// });
+///
+/// scope.dispose();
+/// count.set(1); // the effect no longer runs
+/// ```
+pub fn create_root<T>(f: impl FnOnce(Scope) -> T) -> T {
+    let scope = Scope::with_parent(None);
+    scope.run(|| f(scope.clone()))
+}
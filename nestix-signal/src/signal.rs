@@ -1,5 +1,7 @@
 use std::fmt::Debug;
 
+use crate::ListDiffs;
+
 /// A readable reactive value.
 ///
 /// Calling [`Signal::get`] from inside an effect or computed value may register
@@ -13,6 +15,18 @@ pub trait Signal {
 
     /// Clones this signal into a boxed trait object.
     fn box_clone(&self) -> Box<dyn Signal<Output = Self::Output>>;
+
+    /// Subscribes to the individual changes of a list signal.
+    ///
+    /// Signals such as [`StateVec`](crate::StateVec) return a subscription
+    /// that receives every change made after this call. Other signals return
+    /// `None`, and consumers have to compare whole values instead.
+    fn list_diffs(&self) -> Option<ListDiffs<<Self::Output as IntoIterator>::Item>>
+    where
+        Self::Output: IntoIterator,
+    {
+        None
+    }
 }
 
 impl<T> Clone for Box<dyn Signal<Output = T>> {
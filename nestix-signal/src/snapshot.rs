@@ -0,0 +1,189 @@
+use std::{
+    cell::RefCell,
+    collections::BTreeMap,
+    fmt::{Debug, Display},
+};
+
+use serde::{Serialize, Serializer, de::DeserializeOwned};
+use serde_json::{Map, Value};
+
+use crate::{Computed, Readonly, State, WeakState, batch};
+
+thread_local! {
+    static NAMED_STATES: RefCell<BTreeMap<String, Box<dyn NamedState>>> =
+        const { RefCell::new(BTreeMap::new()) };
+}
+
+impl<T: Serialize> Serialize for State<T> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.borrow().serialize(serializer)
+    }
+}
+
+impl<T: Serialize + Clone + 'static> Serialize for Computed<T> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.get().serialize(serializer)
+    }
+}
+
+impl<T: Serialize> Serialize for Readonly<T> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.get().serialize(serializer)
+    }
+}
+
+/// An error returned by [`snapshot_states`].
+#[derive(Debug)]
+pub struct SnapshotError {
+    name: String,
+    error: serde_json::Error,
+}
+
+impl SnapshotError {
+    /// Returns the name of the state whose value could not be serialized.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+}
+
+impl Display for SnapshotError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "failed to snapshot state `{}`: {}",
+            self.name, self.error
+        )
+    }
+}
+
+impl std::error::Error for SnapshotError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        Some(&self.error)
+    }
+}
+
+/// An error returned by [`restore_states`].
+#[derive(Debug)]
+pub struct RestoreError {
+    name: String,
+    error: serde_json::Error,
+}
+
+impl RestoreError {
+    /// Returns the name of the state whose value could not be deserialized.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+}
+
+impl Display for RestoreError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "failed to restore state `{}`: {}", self.name, self.error)
+    }
+}
+
+impl std::error::Error for RestoreError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        Some(&self.error)
+    }
+}
+
+trait NamedState {
+    fn is_live(&self) -> bool;
+    fn snapshot(&self) -> Option<Result<Value, serde_json::Error>>;
+    /// Deserializes `value` and returns a closure that writes it.
+    fn prepare(&self, value: &Value) -> Result<Option<Box<dyn FnOnce()>>, serde_json::Error>;
+}
+
+impl<T: Serialize + DeserializeOwned + 'static> NamedState for WeakState<T> {
+    fn is_live(&self) -> bool {
+        self.upgrade().is_some()
+    }
+
+    fn snapshot(&self) -> Option<Result<Value, serde_json::Error>> {
+        let state = self.upgrade()?;
+        Some(serde_json::to_value(&*state.borrow_silently()))
+    }
+
+    fn prepare(&self, value: &Value) -> Result<Option<Box<dyn FnOnce()>>, serde_json::Error> {
+        let Some(state) = self.upgrade() else {
+            return Ok(None);
+        };
+        let value = T::deserialize(value)?;
+        Ok(Some(Box::new(move || state.set_unchecked(value))))
+    }
+}
+
+/// Registers `state` under `name` for [`snapshot_states`] and
+/// [`restore_states`].
+///
+/// The registry holds the state weakly, so dropping every handle to it removes
+/// it from snapshots. Registering another state under the same name replaces
+/// the previous registration.
+pub fn register_state<T: Serialize + DeserializeOwned + 'static>(
+    name: impl Into<String>,
+    state: &State<T>,
+) {
+    NAMED_STATES.with_borrow_mut(|states| {
+        states.insert(name.into(), Box::new(state.downgrade()));
+    });
+}
+
+/// Removes the state registered under `name`.
+pub fn unregister_state(name: &str) {
+    NAMED_STATES.with_borrow_mut(|states| {
+        states.remove(name);
+    });
+}
+
+/// Serializes every registered state on the current thread into a JSON object
+/// keyed by name.
+///
+/// Returns an error naming the first state whose value fails to serialize.
+pub fn snapshot_states() -> Result<Value, SnapshotError> {
+    NAMED_STATES.with_borrow_mut(|states| {
+        states.retain(|_, state| state.is_live());
+        let mut entries = Map::new();
+        for (name, state) in states.iter() {
+            let Some(value) = state.snapshot() else {
+                continue;
+            };
+            let value = value.map_err(|error| SnapshotError {
+                name: name.clone(),
+                error,
+            })?;
+            entries.insert(name.clone(), value);
+        }
+        Ok(Value::Object(entries))
+    })
+}
+
+/// Restores registered states from a snapshot created by [`snapshot_states`].
+///
+/// Every value is deserialized before any state is written, so a failure
+/// leaves all states unchanged. The writes happen in a single [`batch`], so
+/// each dependent effect runs once. Names in `snapshot` without a registered
+/// state, and registered states missing from `snapshot`, are skipped.
+pub fn restore_states(snapshot: &Value) -> Result<(), RestoreError> {
+    let writes = NAMED_STATES.with_borrow(|states| {
+        let mut writes = Vec::new();
+        for (name, state) in states {
+            let Some(value) = snapshot.get(name) else {
+                continue;
+            };
+            let write = state.prepare(value).map_err(|error| RestoreError {
+                name: name.clone(),
+                error,
+            })?;
+            writes.extend(write);
+        }
+        Ok(writes)
+    })?;
+
+    batch(|| {
+        for write in writes {
+            write();
+        }
+    });
+    Ok(())
+}
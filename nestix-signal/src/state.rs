@@ -5,7 +5,10 @@ use std::{
     rc::Rc,
 };
 
-use crate::{Effect, Readonly, Signal, current_effect, notify_effect, shared::Shared};
+use crate::{
+    Effect, GraphNodeKind, Readonly, Signal, current_effect, notify_dependents, register_node,
+    shared::Shared,
+};
 
 #[derive(Debug)]
 struct StateData<T> {
@@ -32,6 +35,11 @@ impl<T> State<T> {
         self.data.value.borrow()
     }
 
+    /// Borrows the current value without recording a dependency.
+    pub(crate) fn borrow_silently(&'_ self) -> Ref<'_, T> {
+        self.data.value.borrow()
+    }
+
     /// Replaces the current value and always notifies dependents.
     ///
     /// Unlike [`State::set`], this does not compare the old and new values.
@@ -40,10 +48,18 @@ impl<T> State<T> {
         let location = Location::caller();
         self.data.value.replace(value);
 
-        let dependents = self.data.dependents.borrow().clone();
-        for effect in dependents {
-            notify_effect(&effect, location);
-        }
+        notify_dependents(&self.data.dependents, location);
+    }
+
+    /// Replaces the current value, notifies dependents, and returns the
+    /// previous value.
+    #[track_caller]
+    pub(crate) fn replace(&self, value: T) -> T {
+        let location = Location::caller();
+        let prev = self.data.value.replace(value);
+
+        notify_dependents(&self.data.dependents, location);
+        prev
     }
 
     /// Replaces the current value with the result of `updater`.
@@ -58,10 +74,7 @@ impl<T> State<T> {
         };
         self.data.value.replace(next);
 
-        let dependents = self.data.dependents.borrow().clone();
-        for effect in dependents {
-            notify_effect(&effect, location);
-        }
+        notify_dependents(&self.data.dependents, location);
     }
 
     /// Mutates the current value in place and then notifies dependents.
@@ -72,10 +85,7 @@ impl<T> State<T> {
             let mut value = self.data.value.borrow_mut();
             mutator(&mut value);
         }
-        let dependents = self.data.dependents.borrow().clone();
-        for effect in dependents {
-            notify_effect(&effect, location);
-        }
+        notify_dependents(&self.data.dependents, location);
     }
 }
 
@@ -113,6 +123,24 @@ impl<T: 'static + Clone> Signal for State<T> {
     }
 }
 
+#[cfg(any(feature = "serde", feature = "sync"))]
+impl<T> State<T> {
+    pub(crate) fn downgrade(&self) -> WeakState<T> {
+        WeakState(Rc::downgrade(&self.data))
+    }
+}
+
+/// A weak handle to a [`State`] that does not keep its value alive.
+#[cfg(any(feature = "serde", feature = "sync"))]
+pub(crate) struct WeakState<T>(std::rc::Weak<StateData<T>>);
+
+#[cfg(any(feature = "serde", feature = "sync"))]
+impl<T> WeakState<T> {
+    pub(crate) fn upgrade(&self) -> Option<State<T>> {
+        self.0.upgrade().map(|data| State { data })
+    }
+}
+
 impl<T> Clone for State<T> {
     fn clone(&self) -> Self {
         Self {
@@ -135,11 +163,20 @@ impl<T: Clone + 'static> State<T> {
 }
 
 /// Creates a new reactive state value.
+#[track_caller]
 pub fn create_state<T>(value: T) -> State<T> {
+    let dependents = Shared::new(RefCell::new(HashSet::new()));
+    register_node(
+        GraphNodeKind::State,
+        Location::caller(),
+        Some(&dependents),
+        None,
+    );
+
     State {
         data: Rc::new(StateData {
             value: RefCell::new(value),
-            dependents: Shared::new(RefCell::new(HashSet::new())),
+            dependents,
         }),
     }
 }
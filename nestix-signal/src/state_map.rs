@@ -0,0 +1,259 @@
+use std::{cell::RefCell, collections::HashMap, hash::Hash, rc::Rc};
+
+use crate::{ListDiff, ListDiffs, Signal, list_diff::DiffHub};
+
+struct StateMapData<K, V> {
+    entries: RefCell<Entries<K, V>>,
+    hub: DiffHub<(K, V)>,
+}
+
+/// The ordered entries of a [`StateMap`] and the position of each key.
+struct Entries<K, V> {
+    list: Rc<Vec<(K, V)>>,
+    index: HashMap<K, usize>,
+}
+
+impl<K: Clone + Eq + Hash, V: Clone> Entries<K, V> {
+    fn new(list: Vec<(K, V)>) -> Self {
+        let mut entries = Self {
+            list: Rc::new(list),
+            index: HashMap::new(),
+        };
+        entries.reindex(0..entries.list.len());
+        entries
+    }
+
+    /// Records the position of the entries in `range`.
+    fn reindex(&mut self, range: std::ops::Range<usize>) {
+        for index in range {
+            self.index.insert(self.list[index].0.clone(), index);
+        }
+    }
+}
+
+/// A reactive map that keeps its entries in insertion order and reports each
+/// change it makes.
+///
+/// Like [`StateVec`](crate::StateVec), every write is recorded as a
+/// [`ListDiff`] over the ordered `(key, value)` entries. Lookups by key go
+/// through a hash index.
+///
+/// Cloning a `StateMap` produces another handle to the same map.
+pub struct StateMap<K, V> {
+    data: Rc<StateMapData<K, V>>,
+}
+
+impl<K: Clone + Eq + Hash, V: Clone> StateMap<K, V> {
+    /// Returns the number of entries.
+    pub fn len(&self) -> usize {
+        self.data.hub.track();
+        self.data.entries.borrow().list.len()
+    }
+
+    /// Returns whether the map has no entries.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns a clone of the value for `key`, if there is one.
+    pub fn get(&self, key: &K) -> Option<V> {
+        self.data.hub.track();
+        let entries = self.data.entries.borrow();
+        let index = *entries.index.get(key)?;
+        Some(entries.list[index].1.clone())
+    }
+
+    /// Returns whether the map has an entry for `key`.
+    pub fn contains_key(&self, key: &K) -> bool {
+        self.index_of(key).is_some()
+    }
+
+    /// Returns the position of the entry for `key`, if there is one.
+    pub fn index_of(&self, key: &K) -> Option<usize> {
+        self.data.hub.track();
+        self.data.entries.borrow().index.get(key).copied()
+    }
+
+    /// Returns a clone of all entries in order.
+    pub fn to_vec(&self) -> Vec<(K, V)> {
+        self.with(|entries| entries.to_vec())
+    }
+
+    /// Calls `f` with the entries, recording a dependency if tracking is
+    /// active.
+    ///
+    /// `f` sees the entries as they were when it was called, so it may write
+    /// to the map.
+    pub fn with<R>(&self, f: impl FnOnce(&[(K, V)]) -> R) -> R {
+        self.data.hub.track();
+        let list = self.data.entries.borrow().list.clone();
+        f(&list)
+    }
+
+    /// Sets the value for `key` and returns the previous value.
+    ///
+    /// Existing entries keep their position. New entries are appended.
+    #[track_caller]
+    pub fn insert(&self, key: K, value: V) -> Option<V> {
+        let entry = (key, value);
+        let (diff, previous) = {
+            let mut entries = self.data.entries.borrow_mut();
+            match entries.index.get(&entry.0).copied() {
+                Some(index) => {
+                    let list = Rc::make_mut(&mut entries.list);
+                    let previous = std::mem::replace(&mut list[index], entry.clone());
+                    let diff = ListDiff::Update {
+                        index,
+                        value: entry,
+                    };
+                    (diff, Some(previous.1))
+                }
+                None => {
+                    let index = entries.list.len();
+                    Rc::make_mut(&mut entries.list).push(entry.clone());
+                    entries.index.insert(entry.0.clone(), index);
+                    (
+                        ListDiff::Insert {
+                            index,
+                            value: entry,
+                        },
+                        None,
+                    )
+                }
+            }
+        };
+        self.data.hub.emit([diff]);
+        previous
+    }
+
+    /// Removes the entry for `key` and returns its value.
+    #[track_caller]
+    pub fn remove(&self, key: &K) -> Option<V> {
+        let (index, (_, value)) = {
+            let mut entries = self.data.entries.borrow_mut();
+            let index = entries.index.remove(key)?;
+            let entry = Rc::make_mut(&mut entries.list).remove(index);
+            let len = entries.list.len();
+            entries.reindex(index..len);
+            (index, entry)
+        };
+        self.data.hub.emit([ListDiff::Remove { index }]);
+        Some(value)
+    }
+
+    /// Replaces the value for `key` with the result of `updater`.
+    ///
+    /// Does nothing if the map has no entry for `key`. `updater` runs without
+    /// the map borrowed, so it may read or write the map.
+    #[track_caller]
+    pub fn update(&self, key: &K, updater: impl FnOnce(&V) -> V) {
+        let (list, index) = {
+            let entries = self.data.entries.borrow();
+            let Some(&index) = entries.index.get(key) else {
+                return;
+            };
+            (entries.list.clone(), index)
+        };
+        let value = updater(&list[index].1);
+        drop(list);
+        let (index, entry) = {
+            let mut entries = self.data.entries.borrow_mut();
+            let Some(&index) = entries.index.get(key) else {
+                return;
+            };
+            let list = Rc::make_mut(&mut entries.list);
+            list[index].1 = value;
+            (index, list[index].clone())
+        };
+        self.data.hub.emit([ListDiff::Update {
+            index,
+            value: entry,
+        }]);
+    }
+
+    /// Moves the entry at `from` so that it ends up at index `to`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if either index is out of bounds.
+    #[track_caller]
+    pub fn move_index(&self, from: usize, to: usize) {
+        {
+            let mut entries = self.data.entries.borrow_mut();
+            assert!(to < entries.list.len(), "move target {to} is out of bounds");
+            let list = Rc::make_mut(&mut entries.list);
+            let entry = list.remove(from);
+            list.insert(to, entry);
+            entries.reindex(from.min(to)..from.max(to) + 1);
+        }
+        if from != to {
+            self.data.hub.emit([ListDiff::Move { from, to }]);
+        }
+    }
+
+    /// Removes all entries.
+    #[track_caller]
+    pub fn clear(&self) {
+        self.data.entries.replace(Entries::new(Vec::new()));
+        self.data.hub.emit([ListDiff::Replace]);
+    }
+}
+
+impl<K, V> Clone for StateMap<K, V> {
+    fn clone(&self) -> Self {
+        Self {
+            data: self.data.clone(),
+        }
+    }
+}
+
+impl<K, V> PartialEq for StateMap<K, V> {
+    fn eq(&self, other: &Self) -> bool {
+        Rc::ptr_eq(&self.data, &other.data)
+    }
+}
+
+impl<K: Clone + Eq + Hash + 'static, V: Clone + 'static> Signal for StateMap<K, V> {
+    type Output = Vec<(K, V)>;
+
+    fn get(&self) -> Vec<(K, V)> {
+        self.to_vec()
+    }
+
+    fn box_clone(&self) -> Box<dyn Signal<Output = Vec<(K, V)>>> {
+        Box::new(self.clone())
+    }
+
+    fn list_diffs(&self) -> Option<ListDiffs<<Vec<(K, V)> as IntoIterator>::Item>> {
+        Some(self.data.hub.subscribe())
+    }
+}
+
+/// Creates a reactive map containing `entries` in order.
+///
+/// Later entries replace earlier entries with the same key.
+#[track_caller]
+pub fn create_state_map<K: Clone + Eq + Hash, V: Clone>(
+    entries: impl IntoIterator<Item = (K, V)>,
+) -> StateMap<K, V> {
+    let mut deduped: Vec<(K, V)> = Vec::new();
+    let mut index = HashMap::new();
+    for (key, value) in entries {
+        match index.get(&key) {
+            Some(&position) => deduped[position] = (key, value),
+            None => {
+                index.insert(key.clone(), deduped.len());
+                deduped.push((key, value));
+            }
+        }
+    }
+    StateMap {
+        data: Rc::new(StateMapData {
+            entries: RefCell::new(Entries {
+                list: Rc::new(deduped),
+                index,
+            }),
+            hub: DiffHub::new(),
+        }),
+    }
+}
@@ -0,0 +1,181 @@
+use std::{cell::RefCell, rc::Rc};
+
+use crate::{ListDiff, ListDiffs, Signal, list_diff::DiffHub};
+
+struct StateVecData<T> {
+    items: RefCell<Rc<Vec<T>>>,
+    hub: DiffHub<T>,
+}
+
+/// A reactive list that reports each change it makes.
+///
+/// Reading a `StateVec` records a dependency like reading a [`State`](crate::State)
+/// does. In addition, every write is recorded as a [`ListDiff`], so consumers
+/// such as the `For` component can apply individual changes instead of
+/// comparing the whole list.
+///
+/// Cloning a `StateVec` produces another handle to the same list.
+pub struct StateVec<T> {
+    data: Rc<StateVecData<T>>,
+}
+
+impl<T: Clone> StateVec<T> {
+    /// Returns the number of items.
+    pub fn len(&self) -> usize {
+        self.data.hub.track();
+        self.data.items.borrow().len()
+    }
+
+    /// Returns whether the list has no items.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns a clone of the item at `index`, if there is one.
+    pub fn get(&self, index: usize) -> Option<T> {
+        self.data.hub.track();
+        self.data.items.borrow().get(index).cloned()
+    }
+
+    /// Returns a clone of all items.
+    pub fn to_vec(&self) -> Vec<T> {
+        self.with(|items| items.to_vec())
+    }
+
+    /// Calls `f` with the items, recording a dependency if tracking is active.
+    ///
+    /// `f` sees the items as they were when it was called, so it may write to
+    /// the list.
+    pub fn with<R>(&self, f: impl FnOnce(&[T]) -> R) -> R {
+        self.data.hub.track();
+        let items = self.data.items.borrow().clone();
+        f(&items)
+    }
+
+    /// Appends `value` to the end of the list.
+    #[track_caller]
+    pub fn push(&self, value: T) {
+        let index = self.data.items.borrow().len();
+        self.insert(index, value);
+    }
+
+    /// Inserts `value` at `index`, shifting later items back.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index` is greater than the length of the list.
+    #[track_caller]
+    pub fn insert(&self, index: usize, value: T) {
+        Rc::make_mut(&mut self.data.items.borrow_mut()).insert(index, value.clone());
+        self.data.hub.emit([ListDiff::Insert { index, value }]);
+    }
+
+    /// Removes and returns the item at `index`, shifting later items forward.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index` is out of bounds.
+    #[track_caller]
+    pub fn remove(&self, index: usize) -> T {
+        let value = Rc::make_mut(&mut self.data.items.borrow_mut()).remove(index);
+        self.data.hub.emit([ListDiff::Remove { index }]);
+        value
+    }
+
+    /// Moves the item at `from` so that it ends up at index `to`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if either index is out of bounds.
+    #[track_caller]
+    pub fn move_item(&self, from: usize, to: usize) {
+        {
+            let mut items = self.data.items.borrow_mut();
+            assert!(to < items.len(), "move target {to} is out of bounds");
+            let items = Rc::make_mut(&mut items);
+            let value = items.remove(from);
+            items.insert(to, value);
+        }
+        if from != to {
+            self.data.hub.emit([ListDiff::Move { from, to }]);
+        }
+    }
+
+    /// Replaces the item at `index` with `value`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index` is out of bounds.
+    #[track_caller]
+    pub fn set(&self, index: usize, value: T) {
+        Rc::make_mut(&mut self.data.items.borrow_mut())[index] = value.clone();
+        self.data.hub.emit([ListDiff::Update { index, value }]);
+    }
+
+    /// Replaces the item at `index` with the result of `updater`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index` is out of bounds.
+    #[track_caller]
+    pub fn update(&self, index: usize, updater: impl FnOnce(&T) -> T) {
+        let items = self.data.items.borrow().clone();
+        let value = updater(&items[index]);
+        drop(items);
+        self.set(index, value);
+    }
+
+    /// Replaces all items.
+    #[track_caller]
+    pub fn replace(&self, items: Vec<T>) {
+        self.data.items.replace(Rc::new(items));
+        self.data.hub.emit([ListDiff::Replace]);
+    }
+
+    /// Removes all items.
+    #[track_caller]
+    pub fn clear(&self) {
+        self.replace(Vec::new());
+    }
+}
+
+impl<T> Clone for StateVec<T> {
+    fn clone(&self) -> Self {
+        Self {
+            data: self.data.clone(),
+        }
+    }
+}
+
+impl<T> PartialEq for StateVec<T> {
+    fn eq(&self, other: &Self) -> bool {
+        Rc::ptr_eq(&self.data, &other.data)
+    }
+}
+
+impl<T: Clone + 'static> Signal for StateVec<T> {
+    type Output = Vec<T>;
+
+    fn get(&self) -> Vec<T> {
+        self.to_vec()
+    }
+
+    fn box_clone(&self) -> Box<dyn Signal<Output = Vec<T>>> {
+        Box::new(self.clone())
+    }
+
+    fn list_diffs(&self) -> Option<ListDiffs<<Vec<T> as IntoIterator>::Item>> {
+        Some(self.data.hub.subscribe())
+    }
+}
+
+/// Creates a reactive list containing `items`.
+#[track_caller]
+pub fn create_state_vec<T: Clone>(items: Vec<T>) -> StateVec<T> {
+    StateVec {
+        data: Rc::new(StateVecData {
+            items: RefCell::new(Rc::new(items)),
+            hub: DiffHub::new(),
+        }),
+    }
+}
@@ -0,0 +1,41 @@
+/// A struct whose fields are tracked as separate signals.
+///
+/// Implement it with `#[derive(Store)]` from `nestix`, which generates a
+/// `<Name>Store` handle with one accessor per field. Reading a field through
+/// the handle only subscribes to that field, and writing it only notifies the
+/// readers of that field. Fields marked `#[store(nested)]` are stores
+/// themselves, so their fields are tracked separately too.
+///
+/// ```ignore
+/// #[derive(Clone, PartialEq, Store)]
+/// struct User {
+///     name: String,
+///     age: u32,
+/// }
+///
+/// #[derive(Clone, PartialEq, Store)]
+/// struct AppState {
+///     #[store(nested)]
+///     user: User,
+///     theme: String,
+/// }
+///
+/// let store = create_store(AppState { /* ... */ });
+/// store.user().name().set("Alice".to_string());
+/// let snapshot: AppState = store.get();
+/// ```
+pub trait Store: Sized {
+    /// The handle that exposes each field as its own signal.
+    type Handle: Clone;
+
+    /// Moves this value into a new store handle.
+    fn into_store(self) -> Self::Handle;
+}
+
+/// Creates a store handle for `value`.
+///
+/// See [`Store`] for how fields are tracked.
+#[track_caller]
+pub fn create_store<T: Store>(value: T) -> T::Handle {
+    value.into_store()
+}
@@ -0,0 +1,128 @@
+use std::{cell::RefCell, rc::Rc};
+
+use crate::{State, create_state, on_cleanup};
+
+/// Returns whether a collected resource is loading.
+type IsLoading = Rc<dyn Fn() -> bool>;
+
+thread_local! {
+    static SUSPENSE_TRACKER: RefCell<Option<SuspenseTracker>> = const { RefCell::new(None) };
+}
+
+/// Collects the resources read while it is current.
+///
+/// A tracker becomes current with [`with_suspense`]. Effects created while a
+/// tracker is current keep it, so resources they read later are collected too.
+/// A resource is dropped from the tracker again once every effect, computed
+/// value, and scope that read it has been torn down.
+#[derive(Clone)]
+pub struct SuspenseTracker {
+    data: Rc<SuspenseTrackerData>,
+}
+
+struct SuspenseTrackerData {
+    /// The collected resources, with the number of reads still registered.
+    resources: RefCell<Vec<(IsLoading, usize)>>,
+    version: State<usize>,
+}
+
+impl SuspenseTracker {
+    /// Creates a tracker with no resources.
+    pub fn new() -> Self {
+        Self {
+            data: Rc::new(SuspenseTrackerData {
+                resources: RefCell::new(Vec::new()),
+                version: create_state(0),
+            }),
+        }
+    }
+
+    /// Returns whether any collected resource is loading.
+    ///
+    /// Inside an effect or computed value, this records dependencies on the
+    /// loading state of the collected resources and on newly collected ones.
+    pub fn is_pending(&self) -> bool {
+        self.data.version.get();
+        let resources = self.data.resources.borrow().clone();
+        resources.iter().any(|(is_loading, _)| is_loading())
+    }
+
+    /// Collects a read of a resource until the current effect, computed
+    /// value, or scope is torn down.
+    pub(crate) fn register(&self, is_loading: &IsLoading) {
+        let added = {
+            let mut resources = self.data.resources.borrow_mut();
+            match resources
+                .iter_mut()
+                .find(|(resource, _)| Rc::ptr_eq(resource, is_loading))
+            {
+                Some((_, reads)) => {
+                    *reads += 1;
+                    false
+                }
+                None => {
+                    resources.push((is_loading.clone(), 1));
+                    true
+                }
+            }
+        };
+        if added {
+            self.data.version.update(|version| version + 1);
+        }
+
+        on_cleanup({
+            let tracker = self.clone();
+            let is_loading = is_loading.clone();
+            move || tracker.unregister(&is_loading)
+        });
+    }
+
+    fn unregister(&self, is_loading: &IsLoading) {
+        {
+            let mut resources = self.data.resources.borrow_mut();
+            let Some(index) = resources
+                .iter()
+                .position(|(resource, _)| Rc::ptr_eq(resource, is_loading))
+            else {
+                return;
+            };
+            resources[index].1 -= 1;
+            if resources[index].1 > 0 {
+                return;
+            }
+            resources.remove(index);
+        }
+        self.data.version.update(|version| version + 1);
+    }
+}
+
+impl Default for SuspenseTracker {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Returns the suspense tracker that resource reads are currently reported to.
+pub fn current_suspense() -> Option<SuspenseTracker> {
+    SUSPENSE_TRACKER.with_borrow(|tracker| tracker.clone())
+}
+
+pub(crate) struct SuspenseGuard(Option<SuspenseTracker>);
+
+impl SuspenseGuard {
+    pub(crate) fn set(tracker: Option<SuspenseTracker>) -> Self {
+        Self(SUSPENSE_TRACKER.replace(tracker))
+    }
+}
+
+impl Drop for SuspenseGuard {
+    fn drop(&mut self) {
+        SUSPENSE_TRACKER.replace(self.0.take());
+    }
+}
+
+/// Runs `f` with `tracker` as the current suspense tracker.
+pub fn with_suspense<T>(tracker: Option<SuspenseTracker>, f: impl FnOnce() -> T) -> T {
+    let _guard = SuspenseGuard::set(tracker);
+    f()
+}
@@ -0,0 +1,227 @@
+use std::{
+    any::Any,
+    cell::{Cell, RefCell},
+    collections::HashMap,
+    marker::PhantomData,
+    pin::Pin,
+    sync::{Arc, Mutex},
+    task::{Context, Poll, Waker},
+    thread::{self, ThreadId},
+};
+
+use crate::{State, WeakState, batch, executor::spawn_local};
+
+thread_local! {
+    static INBOX: RefCell<Option<Arc<Inbox>>> = const { RefCell::new(None) };
+    static SYNC_STATES: RefCell<HashMap<u64, Box<dyn Any>>> = RefCell::new(HashMap::new());
+    static NEXT_SYNC_STATE_ID: Cell<u64> = const { Cell::new(0) };
+    /// Identifies the inbox task spawned on the current executor.
+    static INBOX_TASK: Cell<u64> = const { Cell::new(0) };
+}
+
+type SyncWrite = Box<dyn FnOnce() + Send>;
+
+/// Writes posted to a thread by [`SyncState`] handles.
+struct Inbox {
+    owner: ThreadId,
+    writes: Mutex<Vec<SyncWrite>>,
+    waker: Mutex<Option<Waker>>,
+}
+
+impl Inbox {
+    fn post(&self, write: SyncWrite) {
+        if thread::current().id() == self.owner {
+            batch(write);
+            return;
+        }
+
+        self.writes.lock().unwrap().push(write);
+        if let Some(waker) = self.waker.lock().unwrap().take() {
+            waker.wake();
+        }
+    }
+
+    fn apply(&self) {
+        let writes = std::mem::take(&mut *self.writes.lock().unwrap());
+        if writes.is_empty() {
+            return;
+        }
+        batch(|| {
+            for write in writes {
+                write();
+            }
+        });
+    }
+}
+
+/// The task that applies posted writes on the thread that owns the inbox.
+///
+/// The inbox lives as long as its thread, so the task stays pending until
+/// [`set_executor`](crate::set_executor) replaces the executor it runs on. A
+/// new task is then spawned on the new executor and this one completes.
+struct InboxTask {
+    inbox: Arc<Inbox>,
+    id: u64,
+}
+
+impl InboxTask {
+    fn spawn(inbox: Arc<Inbox>) {
+        let id = INBOX_TASK.get() + 1;
+        INBOX_TASK.set(id);
+        spawn_local(Box::pin(InboxTask { inbox, id }));
+    }
+}
+
+impl Future for InboxTask {
+    type Output = ();
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<()> {
+        if INBOX_TASK.get() != self.id {
+            return Poll::Ready(());
+        }
+        // Store the waker before draining, so writes posted while draining
+        // wake the task again.
+        self.inbox.waker.lock().unwrap().replace(cx.waker().clone());
+        self.inbox.apply();
+        Poll::Pending
+    }
+}
+
+/// Moves the inbox task of the current thread, if there is one, to the
+/// executor that was just set.
+pub(crate) fn respawn_inbox_task() {
+    if let Some(inbox) = INBOX.with_borrow(|inbox| inbox.clone()) {
+        InboxTask::spawn(inbox);
+    }
+}
+
+fn current_inbox() -> Arc<Inbox> {
+    INBOX.with_borrow_mut(|inbox| {
+        inbox
+            .get_or_insert_with(|| {
+                let inbox = Arc::new(Inbox {
+                    owner: thread::current().id(),
+                    writes: Mutex::new(Vec::new()),
+                    waker: Mutex::new(None),
+                });
+                InboxTask::spawn(inbox.clone());
+                inbox
+            })
+            .clone()
+    })
+}
+
+fn with_registered_state<T: 'static>(id: u64, f: impl FnOnce(&State<T>)) {
+    let state = SYNC_STATES.with_borrow_mut(|states| {
+        let state = states
+            .get(&id)
+            .and_then(|state| state.downcast_ref::<WeakState<T>>())
+            .and_then(WeakState::upgrade);
+        if state.is_none() {
+            states.remove(&id);
+        }
+        state
+    });
+    if let Some(state) = state {
+        f(&state);
+    }
+}
+
+/// A handle for writing a [`State`] from any thread.
+///
+/// Created with [`State::sync_handle`]. Writes made on the thread that owns the
+/// state apply immediately. Writes from other threads are posted to the owning
+/// thread and applied there, in order and as one batch per wakeup, by a task
+/// running on that thread's [`Executor`](crate::Executor). Writes to a state
+/// that has been dropped are discarded.
+///
+/// Cloning a `SyncState` produces another handle to the same state.
+pub struct SyncState<T> {
+    registration: Arc<Registration>,
+    _marker: PhantomData<fn(T)>,
+}
+
+/// The entry of a state in `SYNC_STATES`, removed when the last handle to it
+/// is dropped.
+struct Registration {
+    id: u64,
+    inbox: Arc<Inbox>,
+}
+
+impl Drop for Registration {
+    fn drop(&mut self) {
+        let id = self.id;
+        let unregister = move || {
+            // The owning thread may already be tearing down its locals.
+            let _ = SYNC_STATES.try_with(|states| states.borrow_mut().remove(&id));
+        };
+        if thread::current().id() == self.inbox.owner {
+            unregister();
+        } else {
+            self.inbox.post(Box::new(unregister));
+        }
+    }
+}
+
+impl<T: Send + 'static> SyncState<T> {
+    /// Replaces the value and always notifies dependents.
+    pub fn set_unchecked(&self, value: T) {
+        self.post(move |state| state.set_unchecked(value));
+    }
+
+    /// Replaces the value with the result of `updater`.
+    pub fn update(&self, updater: impl FnOnce(&T) -> T + Send + 'static) {
+        self.post(move |state| state.update(updater));
+    }
+
+    /// Mutates the value in place and then notifies dependents.
+    pub fn mutate(&self, mutator: impl FnOnce(&mut T) + Send + 'static) {
+        self.post(move |state| state.mutate(mutator));
+    }
+
+    fn post(&self, write: impl FnOnce(&State<T>) + Send + 'static) {
+        let id = self.registration.id;
+        self.registration
+            .inbox
+            .post(Box::new(move || with_registered_state(id, write)));
+    }
+}
+
+impl<T: PartialEq + Send + 'static> SyncState<T> {
+    /// Replaces the value and notifies dependents only when it changes.
+    pub fn set(&self, value: T) {
+        self.post(move |state| state.set(value));
+    }
+}
+
+impl<T> Clone for SyncState<T> {
+    fn clone(&self) -> Self {
+        Self {
+            registration: self.registration.clone(),
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl<T: Send + 'static> State<T> {
+    /// Returns a handle that can write this state from other threads.
+    ///
+    /// The first call on a thread spawns a task on the thread's
+    /// [`Executor`](crate::Executor) that applies writes posted from other
+    /// threads, so the executor must keep running for them to take effect.
+    /// Setting another executor moves the task to it.
+    ///
+    /// # Panics
+    ///
+    /// Panics if no executor has been set for the current thread.
+    pub fn sync_handle(&self) -> SyncState<T> {
+        let inbox = current_inbox();
+        let id = NEXT_SYNC_STATE_ID.with(|id| id.replace(id.get() + 1));
+        SYNC_STATES.with_borrow_mut(|states| states.insert(id, Box::new(self.downgrade())));
+
+        SyncState {
+            registration: Arc::new(Registration { id, inbox }),
+            _marker: PhantomData,
+        }
+    }
+}
@@ -0,0 +1,323 @@
+use std::{
+    cell::{Cell, RefCell},
+    rc::Rc,
+    time::Duration,
+};
+
+use crate::{Readonly, Signal, State, create_state, current_scope, effect, untrack};
+
+thread_local! {
+    static CLOCK: RefCell<Option<Rc<dyn Clock>>> = const { RefCell::new(None) };
+}
+
+/// Identifies a timer started with [`Clock::set_timeout`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct TimerId(pub u64);
+
+/// Runs callbacks after a delay.
+///
+/// Implement this for the event loop of the host application, and install it
+/// with [`set_clock`]. It drives [`debounced`], [`throttled`], and
+/// [`interval`].
+///
+/// The timers of those signals belong to the current [`Scope`](crate::Scope)
+/// and are cleared when it is disposed. Inside a component, that happens when
+/// its element unmounts, inside an effect, before the effect reruns, and
+/// inside a computed value, before it is evaluated again.
+pub trait Clock {
+    /// Calls `callback` once after `delay` has elapsed.
+    fn set_timeout(&self, delay: Duration, callback: Box<dyn FnOnce()>) -> TimerId;
+
+    /// Cancels the timer with the given id.
+    ///
+    /// Clearing a timer that has already fired or been cleared does nothing.
+    fn clear_timeout(&self, id: TimerId);
+}
+
+/// Sets the clock used by time-based signals on the current thread.
+pub fn set_clock(clock: impl Clock + 'static) {
+    CLOCK.with(|current| current.replace(Some(Rc::new(clock))));
+}
+
+fn current_clock() -> Rc<dyn Clock> {
+    let clock = CLOCK.with_borrow(|clock| clock.clone());
+    clock.expect("no clock has been set for this thread")
+}
+
+/// A timer started by a time-based signal, cleared when the scope it was
+/// created in is disposed.
+struct Timer {
+    clock: Rc<dyn Clock>,
+    id: Cell<Option<TimerId>>,
+}
+
+impl Timer {
+    fn new(clock: Rc<dyn Clock>) -> Rc<Self> {
+        let timer = Rc::new(Self {
+            clock,
+            id: Cell::new(None),
+        });
+        if let Some(scope) = current_scope() {
+            let timer = timer.clone();
+            scope.add_cleanup(Box::new(move || timer.clear()));
+        }
+        timer
+    }
+
+    fn start(self: &Rc<Self>, delay: Duration, callback: impl FnOnce() + 'static) {
+        self.clear();
+        let timer = Rc::downgrade(self);
+        let id = self.clock.set_timeout(
+            delay,
+            Box::new(move || {
+                if let Some(timer) = timer.upgrade() {
+                    timer.id.take();
+                }
+                callback();
+            }),
+        );
+        self.id.set(Some(id));
+    }
+
+    fn is_running(&self) -> bool {
+        self.id.get().is_some()
+    }
+
+    fn clear(&self) {
+        if let Some(id) = self.id.take() {
+            self.clock.clear_timeout(id);
+        }
+    }
+}
+
+/// Creates a signal that follows `signal` once it has stopped changing for
+/// `duration`.
+///
+/// Every change restarts the wait, so only the last value of a burst is
+/// taken over. The pending timer is cleared when the current scope is
+/// disposed; see [`Clock`].
+///
+/// # Panics
+///
+/// Panics if no [`Clock`] has been set for the current thread.
+///
+/// ```
+/// use std::time::Duration;
+///
+/// use nestix_signal::{FakeClock, create_state, debounced, set_clock};
+///
+/// let clock = FakeClock::new();
+/// set_clock(clock.clone());
+///
+/// let query = create_state(String::new());
+/// let search = debounced(query.clone(), Duration::from_millis(300));
+///
+/// query.set("ne".to_string());
+/// clock.advance(Duration::from_millis(200));
+/// query.set("nestix".to_string());
+/// clock.advance(Duration::from_millis(200));
+/// assert_eq!(search.get(), "");
+///
+/// clock.advance(Duration::from_millis(100));
+/// assert_eq!(search.get(), "nestix");
+/// ```
+pub fn debounced<S>(signal: S, duration: Duration) -> Readonly<S::Output>
+where
+    S: Signal + 'static,
+    S::Output: Clone + PartialEq + 'static,
+{
+    let output = create_state(untrack(|| signal.get()));
+    let timer = Timer::new(current_clock());
+    let initialized = Cell::new(false);
+
+    effect({
+        let output = output.clone();
+        move || {
+            let value = signal.get();
+            if !initialized.replace(true) {
+                return;
+            }
+            let output = output.clone();
+            timer.start(duration, move || output.set(value));
+        }
+    });
+
+    output.into_readonly()
+}
+
+/// Creates a signal that follows `signal` at most once per `duration`.
+///
+/// The first change is taken over immediately. Changes made while waiting are
+/// collapsed into the latest one, which is taken over when the wait ends. The
+/// pending timer is cleared when the current scope is disposed; see [`Clock`].
+///
+/// # Panics
+///
+/// Panics if no [`Clock`] has been set for the current thread.
+pub fn throttled<S>(signal: S, duration: Duration) -> Readonly<S::Output>
+where
+    S: Signal + 'static,
+    S::Output: Clone + PartialEq + 'static,
+{
+    let throttle = Rc::new(Throttle {
+        output: create_state(untrack(|| signal.get())),
+        trailing: RefCell::new(None),
+        timer: Timer::new(current_clock()),
+        duration,
+    });
+    let initialized = Cell::new(false);
+
+    effect({
+        let throttle = throttle.clone();
+        move || {
+            let value = signal.get();
+            if !initialized.replace(true) {
+                return;
+            }
+            if throttle.timer.is_running() {
+                throttle.trailing.replace(Some(value));
+            } else {
+                throttle.emit(value);
+            }
+        }
+    });
+
+    throttle.output.clone().into_readonly()
+}
+
+struct Throttle<T> {
+    output: State<T>,
+    trailing: RefCell<Option<T>>,
+    timer: Rc<Timer>,
+    duration: Duration,
+}
+
+impl<T: PartialEq + 'static> Throttle<T> {
+    fn emit(self: &Rc<Self>, value: T) {
+        self.output.set(value);
+        let throttle = self.clone();
+        self.timer.start(self.duration, move || {
+            if let Some(value) = throttle.trailing.take() {
+                throttle.emit(value);
+            }
+        });
+    }
+}
+
+/// Creates a signal that counts the number of times `period` has elapsed.
+///
+/// The count starts at `0`. The timer is cleared when the current scope is
+/// disposed; see [`Clock`].
+///
+/// # Panics
+///
+/// Panics if no [`Clock`] has been set for the current thread.
+pub fn interval(period: Duration) -> Readonly<u64> {
+    let ticker = Rc::new(Ticker {
+        count: create_state(0),
+        timer: Timer::new(current_clock()),
+        period,
+    });
+    ticker.schedule();
+    ticker.count.clone().into_readonly()
+}
+
+struct Ticker {
+    count: State<u64>,
+    timer: Rc<Timer>,
+    period: Duration,
+}
+
+impl Ticker {
+    fn schedule(self: &Rc<Self>) {
+        let ticker = self.clone();
+        self.timer.start(self.period, move || {
+            ticker.count.update(|count| count + 1);
+            ticker.schedule();
+        });
+    }
+}
+
+/// A clock whose time only moves when told to.
+///
+/// Timers fire during [`FakeClock::advance`], which makes the clock convenient
+/// for tests. Clones share the same time and timers.
+#[derive(Clone, Default)]
+pub struct FakeClock {
+    inner: Rc<FakeClockInner>,
+}
+
+#[derive(Default)]
+struct FakeClockInner {
+    now: Cell<Duration>,
+    next_id: Cell<u64>,
+    timers: RefCell<Vec<FakeTimer>>,
+}
+
+struct FakeTimer {
+    id: TimerId,
+    deadline: Duration,
+    callback: Box<dyn FnOnce()>,
+}
+
+impl FakeClock {
+    /// Creates a clock at time zero with no timers.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the time elapsed since the clock was created.
+    pub fn now(&self) -> Duration {
+        self.inner.now.get()
+    }
+
+    /// Returns the number of timers that have not fired or been cleared.
+    pub fn pending_timers(&self) -> usize {
+        self.inner.timers.borrow().len()
+    }
+
+    /// Moves the time forward by `duration`, firing every timer that becomes
+    /// due in deadline order.
+    ///
+    /// Timers started by the callbacks fire as well if they become due before
+    /// the new time.
+    pub fn advance(&self, duration: Duration) {
+        let target = self.now() + duration;
+        while let Some(timer) = self.take_due_timer(target) {
+            self.inner.now.set(timer.deadline);
+            (timer.callback)();
+        }
+        self.inner.now.set(target);
+    }
+
+    fn take_due_timer(&self, target: Duration) -> Option<FakeTimer> {
+        let mut timers = self.inner.timers.borrow_mut();
+        let index = timers
+            .iter()
+            .enumerate()
+            .filter(|(_, timer)| timer.deadline <= target)
+            .min_by_key(|(_, timer)| (timer.deadline, timer.id.0))
+            .map(|(index, _)| index)?;
+        Some(timers.remove(index))
+    }
+}
+
+impl Clock for FakeClock {
+    fn set_timeout(&self, delay: Duration, callback: Box<dyn FnOnce()>) -> TimerId {
+        let id = TimerId(self.inner.next_id.get());
+        self.inner.next_id.set(id.0 + 1);
+        self.inner.timers.borrow_mut().push(FakeTimer {
+            id,
+            deadline: self.now() + delay,
+            callback,
+        });
+        id
+    }
+
+    fn clear_timeout(&self, id: TimerId) {
+        self.inner
+            .timers
+            .borrow_mut()
+            .retain(|timer| timer.id != id);
+    }
+}
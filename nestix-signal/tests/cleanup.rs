@@ -0,0 +1,113 @@
+use std::{cell::RefCell, rc::Rc};
+
+use nestix_signal::{computed, create_root, create_state, effect, on_cleanup};
+
+#[test]
+fn effect_cleanups_run_before_each_rerun_and_on_cancel() {
+    let count = create_state(0);
+    let log = Rc::new(RefCell::new(Vec::new()));
+
+    let handle = effect({
+        let count = count.clone();
+        let log = log.clone();
+        move || {
+            let current = count.get();
+            log.borrow_mut().push(format!("run {current}"));
+            let log = log.clone();
+            on_cleanup(move || log.borrow_mut().push(format!("cleanup {current}")));
+        }
+    });
+
+    count.set(1);
+    handle.cancel();
+    handle.cancel();
+
+    assert_eq!(
+        *log.borrow(),
+        vec!["run 0", "cleanup 0", "run 1", "cleanup 1"]
+    );
+}
+
+#[test]
+fn cleanups_do_not_subscribe_the_running_effect() {
+    let count = create_state(0);
+    let other = create_state(0);
+    let runs = Rc::new(RefCell::new(0));
+
+    effect({
+        let count = count.clone();
+        let other = other.clone();
+        let runs = runs.clone();
+        move || {
+            count.get();
+            *runs.borrow_mut() += 1;
+            let other = other.clone();
+            on_cleanup(move || {
+                other.get();
+            });
+        }
+    });
+
+    count.set(1);
+    other.set(1);
+
+    assert_eq!(*runs.borrow(), 2);
+}
+
+#[test]
+fn computed_cleanups_run_when_the_value_is_invalidated() {
+    let count = create_state(0);
+    let log = Rc::new(RefCell::new(Vec::new()));
+    let doubled = computed({
+        let count = count.clone();
+        let log = log.clone();
+        move || {
+            let current = count.get();
+            let log = log.clone();
+            on_cleanup(move || log.borrow_mut().push(current));
+            current * 2
+        }
+    });
+
+    assert_eq!(doubled.get(), 0);
+    assert!(log.borrow().is_empty());
+
+    count.set(1);
+    assert_eq!(*log.borrow(), vec![0]);
+    assert_eq!(doubled.get(), 2);
+}
+
+#[test]
+fn scope_cleanups_run_after_owned_effects_when_disposed() {
+    let log = Rc::new(RefCell::new(Vec::new()));
+
+    let scope = create_root(|scope| {
+        let log = log.clone();
+        effect({
+            let log = log.clone();
+            move || {
+                let log = log.clone();
+                on_cleanup(move || log.borrow_mut().push("effect"));
+            }
+        });
+        on_cleanup(move || log.borrow_mut().push("scope"));
+        scope
+    });
+
+    assert!(log.borrow().is_empty());
+    scope.dispose();
+
+    assert_eq!(*log.borrow(), vec!["effect", "scope"]);
+}
+
+#[test]
+fn cleanups_registered_outside_any_owner_are_dropped() {
+    let log = Rc::new(RefCell::new(Vec::new()));
+
+    on_cleanup({
+        let log = log.clone();
+        move || log.borrow_mut().push("called")
+    });
+
+    assert!(log.borrow().is_empty());
+}
@@ -0,0 +1,126 @@
+use std::{
+    panic::{AssertUnwindSafe, catch_unwind},
+    sync::{Mutex, MutexGuard},
+};
+
+use nestix_signal::{CycleConfig, CyclePolicy, create_state, effect, set_cycle_config};
+
+/// Cycle configuration is process-wide, so tests that change it run one at a
+/// time.
+static CONFIG_LOCK: Mutex<()> = Mutex::new(());
+
+fn with_cycle_config(policy: CyclePolicy, max_reruns: usize) -> MutexGuard<'static, ()> {
+    let guard = CONFIG_LOCK
+        .lock()
+        .unwrap_or_else(|error| error.into_inner());
+    set_cycle_config(CycleConfig { policy, max_reruns });
+    guard
+}
+
+fn panic_message(payload: Box<dyn std::any::Any + Send>) -> String {
+    match payload.downcast::<String>() {
+        Ok(message) => *message,
+        Err(payload) => payload.downcast_ref::<&str>().unwrap().to_string(),
+    }
+}
+
+#[test]
+fn converging_self_updates_settle_under_the_warn_policy() {
+    let _config = with_cycle_config(CyclePolicy::Warn, 100);
+    let count = create_state(0);
+
+    effect({
+        let count = count.clone();
+        move || count.set((count.get() + 1).min(5))
+    });
+
+    assert_eq!(count.get(), 5);
+}
+
+#[test]
+fn warn_policy_stops_runaway_effects_after_max_reruns() {
+    let _config = with_cycle_config(CyclePolicy::Warn, 10);
+    let count = create_state(0);
+
+    effect({
+        let count = count.clone();
+        move || count.set(count.get() + 1)
+    });
+
+    assert_eq!(count.get(), 11);
+
+    // The effect is still subscribed and runs again on the next update.
+    count.set(100);
+    assert_eq!(count.get(), 110);
+}
+
+#[test]
+fn skip_policy_ignores_reruns_triggered_by_the_effect_itself() {
+    let _config = with_cycle_config(CyclePolicy::Skip, 100);
+    let count = create_state(0);
+
+    effect({
+        let count = count.clone();
+        move || count.set(count.get() + 1)
+    });
+    assert_eq!(count.get(), 1);
+
+    count.set(10);
+    assert_eq!(count.get(), 11);
+}
+
+#[test]
+fn skip_policy_stops_cycles_between_effects_after_max_reruns() {
+    let _config = with_cycle_config(CyclePolicy::Skip, 3);
+    let a = create_state(0);
+    let b = create_state(0);
+
+    effect({
+        let a = a.clone();
+        let b = b.clone();
+        move || b.set(a.get() + 1)
+    });
+    effect({
+        let a = a.clone();
+        let b = b.clone();
+        move || a.set(b.get() + 1)
+    });
+
+    assert!(a.get() < 20);
+    assert!(b.get() < 20);
+}
+
+#[test]
+fn panic_policy_reports_the_chain_of_writes() {
+    let _config = with_cycle_config(CyclePolicy::Panic, 100);
+    let a = create_state(0);
+    let b = create_state(0);
+
+    effect({
+        let a = a.clone();
+        let b = b.clone();
+        move || b.set(a.get() + 1)
+    });
+    let first_line = line!() - 5;
+
+    let payload = catch_unwind(AssertUnwindSafe(|| {
+        effect({
+            let a = a.clone();
+            let b = b.clone();
+            move || a.set(b.get() + 1)
+        });
+    }))
+    .unwrap_err();
+    let second_line = line!() - 7;
+
+    let message = panic_message(payload);
+    assert!(message.starts_with("cyclic update detected"), "{message}");
+    assert!(
+        message.contains(&format!("effect at {}:{first_line}", file!())),
+        "{message}"
+    );
+    assert!(
+        message.contains(&format!("effect at {}:{second_line}", file!())),
+        "{message}"
+    );
+}
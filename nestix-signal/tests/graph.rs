@@ -0,0 +1,113 @@
+use nestix_signal::{
+    DebugConfig, GraphEdge, GraphNodeKind, computed, create_state, debug_signals, effect,
+    signal_graph,
+};
+
+fn track_graph() {
+    debug_signals(DebugConfig {
+        track_graph: true,
+        ..Default::default()
+    });
+}
+
+#[test]
+fn signal_graph_lists_nodes_and_dependencies() {
+    track_graph();
+
+    let count = create_state(1);
+    let count_line = line!() - 1;
+    let doubled = computed({
+        let count = count.clone();
+        move || count.get() * 2
+    });
+    let doubled_line = line!() - 4;
+    let _handle = effect({
+        let count = count.clone();
+        let doubled = doubled.clone();
+        move || {
+            count.get();
+            doubled.get();
+        }
+    });
+    let effect_line = line!() - 8;
+
+    let graph = signal_graph();
+    let count = graph.node_at(file!(), count_line).unwrap();
+    let doubled = graph.node_at(file!(), doubled_line).unwrap();
+    let effect = graph.node_at(file!(), effect_line).unwrap();
+
+    assert_eq!(count.kind, GraphNodeKind::State);
+    assert_eq!(doubled.kind, GraphNodeKind::Computed);
+    assert_eq!(effect.kind, GraphNodeKind::Effect);
+    assert_eq!(graph.sources(doubled.id), vec![count.id]);
+    assert_eq!(graph.dependents(count.id), vec![doubled.id, effect.id]);
+    assert_eq!(graph.sources(effect.id), vec![count.id, doubled.id]);
+}
+
+#[test]
+fn signal_graph_omits_dropped_signals_and_cancelled_effects() {
+    track_graph();
+
+    let count = create_state(1);
+    let handle = effect({
+        let count = count.clone();
+        move || {
+            count.get();
+        }
+    });
+    let temporary = create_state(0);
+    drop(temporary);
+
+    assert_eq!(signal_graph().nodes.len(), 2);
+
+    handle.cancel();
+    let graph = signal_graph();
+
+    assert_eq!(graph.nodes.len(), 1);
+    assert_eq!(graph.nodes[0].kind, GraphNodeKind::State);
+    assert!(graph.edges.is_empty());
+}
+
+#[test]
+fn signal_graph_exports_dot_and_json() {
+    track_graph();
+
+    let count = create_state(1);
+    let line = line!() - 1;
+    let _handle = effect(move || {
+        count.get();
+    });
+
+    let graph = signal_graph();
+    let state = graph.node_at(file!(), line).unwrap().id;
+    let effect = graph.node_at(file!(), line + 2).unwrap().id;
+    assert_eq!(
+        graph.edges,
+        vec![GraphEdge {
+            from: state,
+            to: effect
+        }]
+    );
+
+    let file = file!();
+    assert_eq!(
+        graph.to_dot(),
+        format!(
+            "digraph signals {{\n    \
+             n{state} [label=\"state {file}:{line}:17\", shape=ellipse];\n    \
+             n{effect} [label=\"effect {file}:{}:19\", shape=hexagon];\n    \
+             n{state} -> n{effect};\n}}\n",
+            line + 2
+        )
+    );
+    assert_eq!(
+        graph.to_json(),
+        format!(
+            "{{\"nodes\":[\
+             {{\"id\":{state},\"kind\":\"state\",\"file\":\"{file}\",\"line\":{line},\"column\":17}},\
+             {{\"id\":{effect},\"kind\":\"effect\",\"file\":\"{file}\",\"line\":{},\"column\":19}}],\
+             \"edges\":[{{\"from\":{state},\"to\":{effect}}}]}}",
+            line + 2
+        )
+    );
+}
@@ -0,0 +1,93 @@
+use std::{cell::Cell, rc::Rc};
+
+use nestix_signal::{batch, create_history_state, create_state, effect};
+
+#[test]
+fn undo_and_redo_restore_previous_values() {
+    let count = create_history_state(0, 10);
+
+    count.set(1);
+    count.update(|count| count + 1);
+    count.mutate(|count| *count *= 10);
+    assert_eq!(count.get(), 20);
+
+    assert!(count.undo());
+    assert_eq!(count.get(), 2);
+    assert!(count.undo());
+    assert!(count.undo());
+    assert_eq!(count.get(), 0);
+    assert!(!count.undo());
+
+    assert!(count.redo());
+    assert_eq!(count.get(), 1);
+
+    count.set(5);
+    assert!(!count.redo());
+    assert!(count.undo());
+    assert_eq!(count.get(), 1);
+}
+
+#[test]
+fn writes_inside_one_batch_form_one_entry() {
+    let text = create_history_state(String::new(), 10);
+
+    batch(|| {
+        text.set("a".to_string());
+        text.mutate(|text| text.push('b'));
+        text.update(|text| format!("{text}c"));
+    });
+    text.set("abcd".to_string());
+
+    text.undo();
+    assert_eq!(text.get(), "abc");
+    text.undo();
+    assert_eq!(text.get(), "");
+    assert!(!text.can_undo());
+}
+
+#[test]
+fn history_keeps_at_most_limit_entries() {
+    let count = create_state(0).with_history(2);
+
+    for value in 1..=5 {
+        count.set(value);
+    }
+
+    assert!(count.undo());
+    assert!(count.undo());
+    assert!(!count.undo());
+    assert_eq!(count.get(), 3);
+}
+
+#[test]
+fn can_undo_and_can_redo_are_reactive() {
+    let count = create_history_state(0, 10);
+    let flags = Rc::new(Cell::new((false, false)));
+    let runs = Rc::new(Cell::new(0));
+
+    effect({
+        let count = count.clone();
+        let flags = flags.clone();
+        let runs = runs.clone();
+        move || {
+            flags.set((count.can_undo(), count.can_redo()));
+            runs.set(runs.get() + 1);
+        }
+    });
+    assert_eq!(flags.get(), (false, false));
+
+    count.set(1);
+    assert_eq!(flags.get(), (true, false));
+
+    count.set(2);
+    assert_eq!(runs.get(), 2);
+
+    count.undo();
+    assert_eq!(flags.get(), (true, true));
+
+    count.undo();
+    assert_eq!(flags.get(), (false, true));
+
+    count.clear_history();
+    assert_eq!(flags.get(), (false, false));
+}
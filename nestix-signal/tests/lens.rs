@@ -0,0 +1,177 @@
+use std::{
+    cell::{Cell, RefCell},
+    rc::Rc,
+};
+
+use nestix_signal::{Scope, State, create_state, effect};
+
+#[derive(Debug, Clone, PartialEq)]
+struct Profile {
+    name: String,
+    address: Address,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+struct Address {
+    city: String,
+    zip: String,
+}
+
+fn profile() -> Profile {
+    Profile {
+        name: "Alice".to_string(),
+        address: Address {
+            city: "Oslo".to_string(),
+            zip: "0150".to_string(),
+        },
+    }
+}
+
+#[test]
+fn lens_writes_update_the_parent_state() {
+    let profile = create_state(profile());
+    let name = profile.lens(
+        |profile| profile.name.clone(),
+        |profile, name| profile.name = name,
+    );
+    let observed = Rc::new(RefCell::new(String::new()));
+
+    effect({
+        let profile = profile.clone();
+        let observed = observed.clone();
+        move || *observed.borrow_mut() = profile.get().name
+    });
+
+    name.set("Bob".to_string());
+
+    assert_eq!(name.get(), "Bob");
+    assert_eq!(*observed.borrow(), "Bob");
+}
+
+#[test]
+fn lens_readers_only_rerun_when_their_part_changes() {
+    let profile = create_state(profile());
+    let city = profile.map_mut(|profile| &mut profile.address.city);
+    let runs = Rc::new(Cell::new(0));
+
+    effect({
+        let city = city.clone();
+        let runs = runs.clone();
+        move || {
+            city.get();
+            runs.set(runs.get() + 1);
+        }
+    });
+
+    profile.mutate(|profile| profile.name = "Bob".to_string());
+    assert_eq!(runs.get(), 1);
+
+    profile.mutate(|profile| profile.address.city = "Bergen".to_string());
+    assert_eq!(runs.get(), 2);
+    assert_eq!(city.get(), "Bergen");
+}
+
+#[test]
+fn lenses_compose_over_nested_fields() {
+    let profile = create_state(profile());
+    let address = profile.lens(
+        |profile| profile.address.clone(),
+        |profile, address| profile.address = address,
+    );
+    let zip = address.lens(
+        |address| address.zip.clone(),
+        |address, zip| address.zip = zip,
+    );
+
+    zip.update(|zip| format!("{zip}-1"));
+
+    assert_eq!(profile.get().address.zip, "0150-1");
+    assert_eq!(profile.get().address.city, "Oslo");
+}
+
+#[test]
+fn setting_an_unchanged_value_does_not_notify() {
+    let count = create_state(1);
+    let lens = count.clone().into_lens();
+    let runs = Rc::new(Cell::new(0));
+
+    effect({
+        let count = count.clone();
+        let runs = runs.clone();
+        move || {
+            count.get();
+            runs.set(runs.get() + 1);
+        }
+    });
+
+    lens.set(1);
+    assert_eq!(runs.get(), 1);
+
+    lens.set(2);
+    assert_eq!(runs.get(), 2);
+    assert_eq!(count.get(), 2);
+}
+
+#[test]
+fn lenses_can_be_read_while_the_state_is_borrowed() {
+    let profile = create_state(profile());
+    let city = profile.map_mut(|profile| &mut profile.address.city);
+    let name = profile.lens(
+        |profile| profile.name.clone(),
+        |profile, name| profile.name = name,
+    );
+
+    let borrowed = profile.borrow();
+
+    assert_eq!(city.get(), "Oslo");
+    assert_eq!(name.get(), "Alice");
+    assert_eq!(borrowed.name, "Alice");
+}
+
+#[test]
+fn untracked_lens_reads_do_not_evaluate_eagerly() {
+    let count = create_state(1);
+    let reads = Rc::new(Cell::new(0));
+    let doubled = count.lens(
+        {
+            let reads = reads.clone();
+            move |count| {
+                reads.set(reads.get() + 1);
+                count * 2
+            }
+        },
+        |count, doubled| *count = doubled / 2,
+    );
+
+    assert_eq!(reads.get(), 0);
+
+    count.set(2);
+    count.set(3);
+    assert_eq!(reads.get(), 0);
+    assert_eq!(doubled.get(), 6);
+    assert_eq!(reads.get(), 1);
+}
+
+#[test]
+fn states_converted_from_lenses_stay_in_sync_with_the_parent() {
+    let profile = create_state(profile());
+    let scope = Scope::new();
+    let name: State<String> = scope.run(|| {
+        profile
+            .lens(
+                |profile| profile.name.clone(),
+                |profile, name| profile.name = name,
+            )
+            .into()
+    });
+
+    name.set("Bob".to_string());
+    assert_eq!(profile.get().name, "Bob");
+
+    profile.mutate(|profile| profile.name = "Carol".to_string());
+    assert_eq!(name.get(), "Carol");
+
+    scope.dispose();
+    name.set("Dave".to_string());
+    assert_eq!(profile.get().name, "Carol");
+}
@@ -0,0 +1,124 @@
+use std::{cell::Cell, rc::Rc};
+
+use nestix_signal::{ListDiff, Signal, create_state_map, create_state_vec, effect};
+
+#[test]
+fn state_vec_reports_each_change_to_subscribers() {
+    let items = create_state_vec(vec!["a", "b"]);
+    let diffs = items.list_diffs().unwrap();
+
+    items.push("c");
+    items.remove(0);
+    items.move_item(1, 0);
+    items.set(1, "d");
+    items.replace(vec!["e"]);
+
+    assert_eq!(
+        diffs.take(),
+        vec![
+            ListDiff::Insert {
+                index: 2,
+                value: "c"
+            },
+            ListDiff::Remove { index: 0 },
+            ListDiff::Move { from: 1, to: 0 },
+            ListDiff::Update {
+                index: 1,
+                value: "d"
+            },
+            ListDiff::Replace,
+        ]
+    );
+    assert!(diffs.take().is_empty());
+    assert_eq!(items.to_vec(), vec!["e"]);
+}
+
+#[test]
+fn state_vec_notifies_readers_on_every_change() {
+    let items = create_state_vec(vec![1, 2, 3]);
+    let sum = Rc::new(Cell::new(0));
+
+    effect({
+        let items = items.clone();
+        let sum = sum.clone();
+        move || sum.set(items.with(|items| items.iter().sum()))
+    });
+    assert_eq!(sum.get(), 6);
+
+    items.update(0, |value| value * 10);
+    assert_eq!(sum.get(), 15);
+
+    items.insert(1, 5);
+    assert_eq!(sum.get(), 20);
+
+    items.clear();
+    assert_eq!(sum.get(), 0);
+}
+
+#[test]
+fn state_map_reports_changes_as_ordered_entries() {
+    let map = create_state_map([("a", 1), ("b", 2)]);
+    let diffs = map.list_diffs().unwrap();
+
+    assert_eq!(map.insert("c", 3), None);
+    assert_eq!(map.insert("a", 10), Some(1));
+    map.update(&"b", |value| value + 1);
+    assert_eq!(map.remove(&"a"), Some(10));
+    map.move_index(1, 0);
+
+    assert_eq!(
+        diffs.take(),
+        vec![
+            ListDiff::Insert {
+                index: 2,
+                value: ("c", 3)
+            },
+            ListDiff::Update {
+                index: 0,
+                value: ("a", 10)
+            },
+            ListDiff::Update {
+                index: 1,
+                value: ("b", 3)
+            },
+            ListDiff::Remove { index: 0 },
+            ListDiff::Move { from: 1, to: 0 },
+        ]
+    );
+    assert_eq!(map.to_vec(), vec![("c", 3), ("b", 3)]);
+    assert_eq!(map.index_of(&"b"), Some(1));
+}
+
+#[test]
+fn state_map_keeps_key_positions_after_removes_and_moves() {
+    let map = create_state_map([("a", 1), ("b", 2), ("c", 3), ("d", 4)]);
+
+    map.remove(&"b");
+    map.move_index(2, 0);
+
+    assert_eq!(map.to_vec(), vec![("d", 4), ("a", 1), ("c", 3)]);
+    assert_eq!(map.index_of(&"d"), Some(0));
+    assert_eq!(map.index_of(&"a"), Some(1));
+    assert_eq!(map.index_of(&"c"), Some(2));
+    assert_eq!(map.get(&"c"), Some(3));
+    assert!(!map.contains_key(&"b"));
+}
+
+#[test]
+fn collection_callbacks_may_write_to_their_collection() {
+    let map = create_state_map([("a", 1)]);
+    map.update(&"a", |value| {
+        map.insert("b", *value);
+        value + 1
+    });
+    map.with(|entries| map.insert("c", entries.len()));
+    assert_eq!(map.to_vec(), vec![("a", 2), ("b", 1), ("c", 2)]);
+
+    let items = create_state_vec(vec![1]);
+    items.update(0, |value| {
+        items.push(*value);
+        value + 1
+    });
+    items.with(|values| items.push(values.len() as i32));
+    assert_eq!(items.to_vec(), vec![2, 1, 2]);
+}
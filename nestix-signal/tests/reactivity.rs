@@ -1,6 +1,13 @@
-use std::{cell::Cell, rc::Rc};
+use std::{
+    cell::{Cell, RefCell},
+    panic::AssertUnwindSafe,
+    rc::Rc,
+};
 
-use nestix_signal::{Readonly, Signal, batch, computed, create_state, effect, untrack};
+use nestix_signal::{
+    CaughtPanic, Computed, Readonly, Shared, Signal, batch, computed, create_state,
+    current_error_handler, effect, memo, untrack, with_error_handler,
+};
 
 #[test]
 fn state_notifies_effects_when_value_changes() {
@@ -266,6 +273,89 @@ fn cancelled_effects_do_not_run_when_a_batch_flushes() {
     assert_eq!(runs.get(), 1);
 }
 
+#[test]
+fn effects_reading_a_state_and_its_derived_value_run_once_per_write() {
+    let count = create_state(1);
+    let doubled = computed({
+        let count = count.clone();
+        move || count.get() * 2
+    });
+    let observed = Rc::new(RefCell::new(Vec::new()));
+
+    effect({
+        let count = count.clone();
+        let doubled = doubled.clone();
+        let observed = observed.clone();
+        move || observed.borrow_mut().push((count.get(), doubled.get()))
+    });
+
+    count.set(2);
+    count.set(3);
+
+    assert_eq!(*observed.borrow(), vec![(1, 2), (2, 4), (3, 6)]);
+}
+
+#[test]
+fn effects_run_after_the_effects_they_depend_on() {
+    let count = create_state(1);
+    let label = create_state(String::from("1"));
+    let doubled = computed({
+        let count = count.clone();
+        move || count.get() * 2
+    });
+    let observed = Rc::new(RefCell::new(Vec::new()));
+
+    // Created first, so it is notified first, but it reads a computed value
+    // and a state written by the effect below.
+    effect({
+        let doubled = doubled.clone();
+        let label = label.clone();
+        let observed = observed.clone();
+        move || observed.borrow_mut().push((doubled.get(), label.get()))
+    });
+    effect({
+        let count = count.clone();
+        let label = label.clone();
+        move || label.set(count.get().to_string())
+    });
+
+    count.set(2);
+
+    assert_eq!(
+        *observed.borrow(),
+        vec![(2, String::from("1")), (4, String::from("2"))]
+    );
+}
+
+#[test]
+fn writes_inside_effects_are_applied_after_the_effect_finishes() {
+    let count = create_state(1);
+    let mirror = create_state(0);
+    let seen_inside = Rc::new(Cell::new(0));
+    let mirrored = Rc::new(Cell::new(0));
+
+    effect({
+        let mirror = mirror.clone();
+        let mirrored = mirrored.clone();
+        move || mirrored.set(mirror.get())
+    });
+    effect({
+        let count = count.clone();
+        let mirror = mirror.clone();
+        let mirrored = mirrored.clone();
+        let seen_inside = seen_inside.clone();
+        move || {
+            mirror.set(count.get());
+            seen_inside.set(mirrored.get());
+        }
+    });
+
+    count.set(2);
+
+    assert_eq!(seen_inside.get(), 1);
+    assert_eq!(mirrored.get(), 2);
+}
+
 #[test]
 fn computed_values_are_lazy_cached_and_invalidated_by_dependencies() {
     let count = create_state(2);
@@ -364,6 +454,85 @@ fn effects_refresh_their_dependencies_when_branches_change() {
     assert_eq!(runs.get(), 3);
 }
 
+#[test]
+fn memo_notifies_dependents_only_when_its_value_changes() {
+    let count = create_state(0);
+    let is_even = memo({
+        let count = count.clone();
+        move || count.get() % 2 == 0
+    });
+    let runs = Rc::new(Cell::new(0));
+
+    effect({
+        let is_even = is_even.clone();
+        let runs = runs.clone();
+        move || {
+            is_even.get();
+            runs.set(runs.get() + 1);
+        }
+    });
+
+    count.set(2);
+    count.set(4);
+    assert_eq!(runs.get(), 1);
+
+    count.set(5);
+    assert!(!is_even.get());
+    assert_eq!(runs.get(), 2);
+}
+
+#[test]
+fn computed_with_eq_uses_the_custom_comparator() {
+    let name = create_state(String::from("Ada"));
+    let upper = Computed::with_eq(
+        {
+            let name = name.clone();
+            move || name.get()
+        },
+        |a: &String, b: &String| a.eq_ignore_ascii_case(b),
+    );
+    let observed = Rc::new(RefCell::new(Vec::new()));
+
+    effect({
+        let upper = upper.clone();
+        let observed = observed.clone();
+        move || observed.borrow_mut().push(upper.get())
+    });
+
+    name.set(String::from("ADA"));
+    name.set(String::from("Grace"));
+
+    assert_eq!(*observed.borrow(), vec!["Ada", "Grace"]);
+}
+
+#[test]
+fn memo_values_stay_fresh_inside_batches() {
+    let count = create_state(1);
+    let is_even = memo({
+        let count = count.clone();
+        move || count.get() % 2 == 0
+    });
+    let runs = Rc::new(Cell::new(0));
+
+    effect({
+        let is_even = is_even.clone();
+        let runs = runs.clone();
+        move || {
+            is_even.get();
+            runs.set(runs.get() + 1);
+        }
+    });
+
+    batch(|| {
+        count.set(2);
+        assert!(is_even.get());
+        count.set(3);
+        assert!(!is_even.get());
+    });
+
+    assert_eq!(runs.get(), 1);
+}
+
 #[test]
 fn readonly_signal_tracks_wrapped_signal() {
     let source = create_state(7);
@@ -421,3 +590,58 @@ fn untrack_reads_without_subscribing_the_current_effect() {
     assert_eq!(observed.get(), 22);
     assert_eq!(runs.get(), 2);
 }
+
+#[test]
+fn panicking_effect_restores_tracking_state() {
+    let count = create_state(1);
+    let runs = Rc::new(Cell::new(0));
+    let result = std::panic::catch_unwind(AssertUnwindSafe(|| {
+        let runs = runs.clone();
+        effect(move || {
+            runs.set(runs.get() + 1);
+            panic!("effect failed");
+        });
+    }));
+    assert!(result.is_err());
+
+    // A read outside of any effect must not subscribe the failed effect.
+    count.get();
+    count.set(2);
+
+    assert_eq!(runs.get(), 1);
+}
+
+#[test]
+fn effects_report_panics_to_the_error_handler_they_were_created_with() {
+    let count = create_state(1);
+    let errors = Rc::new(RefCell::new(Vec::new()));
+    let handler = Shared::from(Rc::new({
+        let errors = errors.clone();
+        move |error: CaughtPanic| errors.borrow_mut().push(error.message().to_string())
+    }) as Rc<dyn Fn(CaughtPanic)>);
+
+    with_error_handler(Some(handler), || {
+        effect({
+            let count = count.clone();
+            move || {
+                let count = count.get();
+                effect(move || {
+                    if count == 3 {
+                        panic!("inner count is {count}");
+                    }
+                });
+                if count == 2 {
+                    panic!("count is {count}");
+                }
+            }
+        });
+    });
+
+    assert!(current_error_handler().is_none());
+    assert!(errors.borrow().is_empty());
+
+    count.set(2);
+    count.set(3);
+
+    assert_eq!(*errors.borrow(), vec!["count is 2", "inner count is 3"]);
+}
@@ -0,0 +1,188 @@
+use std::{
+    cell::RefCell,
+    pin::Pin,
+    rc::Rc,
+    task::{Context, Poll, Waker},
+};
+
+use nestix_signal::{
+    LocalExecutor, Resource, ResourceState, State, SuspenseTracker, create_resource, create_state,
+    set_executor, with_suspense,
+};
+
+struct DeferredData<T> {
+    value: Option<T>,
+    waker: Option<Waker>,
+}
+
+/// A future that completes when [`Deferred::resolve`] is called.
+struct Deferred<T> {
+    data: Rc<RefCell<DeferredData<T>>>,
+}
+
+impl<T> Deferred<T> {
+    fn new() -> Self {
+        Self {
+            data: Rc::new(RefCell::new(DeferredData {
+                value: None,
+                waker: None,
+            })),
+        }
+    }
+
+    fn resolve(&self, value: T) {
+        let mut data = self.data.borrow_mut();
+        data.value = Some(value);
+        if let Some(waker) = data.waker.take() {
+            waker.wake();
+        }
+    }
+}
+
+impl<T> Clone for Deferred<T> {
+    fn clone(&self) -> Self {
+        Self {
+            data: self.data.clone(),
+        }
+    }
+}
+
+impl<T> Future for Deferred<T> {
+    type Output = T;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<T> {
+        let mut data = self.data.borrow_mut();
+        match data.value.take() {
+            Some(value) => Poll::Ready(value),
+            None => {
+                data.waker = Some(cx.waker().clone());
+                Poll::Pending
+            }
+        }
+    }
+}
+
+type Requests = Rc<RefCell<Vec<(i32, Deferred<Result<String, String>>)>>>;
+
+fn user_resource(id: &State<i32>) -> (Resource<String, String>, Requests) {
+    let requests: Requests = Rc::new(RefCell::new(Vec::new()));
+    let resource = create_resource(id.clone(), {
+        let requests = requests.clone();
+        move |id| {
+            let deferred = Deferred::new();
+            requests.borrow_mut().push((id, deferred.clone()));
+            deferred
+        }
+    });
+    (resource, requests)
+}
+
+fn resolve(requests: &Requests, index: usize, result: Result<String, String>) {
+    let deferred = requests.borrow()[index].1.clone();
+    deferred.resolve(result);
+}
+
+#[test]
+fn resource_loads_and_refetches_when_source_changes() {
+    let executor = LocalExecutor::new();
+    set_executor(executor.clone());
+    let id = create_state(1);
+    let (user, requests) = user_resource(&id);
+
+    executor.run_until_stalled();
+    assert!(user.loading());
+    assert_eq!(user.get(), None);
+    assert_eq!(requests.borrow()[0].0, 1);
+
+    resolve(&requests, 0, Ok("ada".to_string()));
+    executor.run_until_stalled();
+
+    assert_eq!(user.state(), ResourceState::Ready("ada".to_string()));
+    assert_eq!(user.get(), Some("ada".to_string()));
+
+    id.set(2);
+
+    assert!(user.loading());
+    assert_eq!(requests.borrow()[1].0, 2);
+
+    resolve(&requests, 1, Ok("grace".to_string()));
+    executor.run_until_stalled();
+
+    assert_eq!(user.get(), Some("grace".to_string()));
+    assert_eq!(executor.pending_tasks(), 0);
+}
+
+#[test]
+fn superseded_fetches_are_discarded() {
+    let executor = LocalExecutor::new();
+    set_executor(executor.clone());
+    let id = create_state(1);
+    let (user, requests) = user_resource(&id);
+
+    id.set(2);
+    resolve(&requests, 0, Ok("ada".to_string()));
+    executor.run_until_stalled();
+
+    assert!(user.loading());
+
+    resolve(&requests, 1, Ok("grace".to_string()));
+    executor.run_until_stalled();
+
+    assert_eq!(user.get(), Some("grace".to_string()));
+}
+
+#[test]
+fn failed_fetches_expose_the_error_and_can_be_refetched() {
+    let executor = LocalExecutor::new();
+    set_executor(executor.clone());
+    let id = create_state(1);
+    let (user, requests) = user_resource(&id);
+
+    resolve(&requests, 0, Err("offline".to_string()));
+    executor.run_until_stalled();
+
+    assert_eq!(user.error(), Some("offline".to_string()));
+    assert_eq!(user.get(), None);
+
+    user.refetch();
+
+    assert!(user.loading());
+    assert_eq!(requests.borrow()[1].0, 1);
+
+    resolve(&requests, 1, Ok("ada".to_string()));
+    executor.run_until_stalled();
+
+    assert_eq!(user.error(), None);
+    assert_eq!(user.get(), Some("ada".to_string()));
+}
+
+#[test]
+fn suspense_tracker_is_pending_while_a_read_resource_loads() {
+    let executor = LocalExecutor::new();
+    set_executor(executor.clone());
+    let id = create_state(1);
+    let (user, requests) = user_resource(&id);
+    let tracker = SuspenseTracker::new();
+
+    assert!(!tracker.is_pending());
+
+    with_suspense(Some(tracker.clone()), || user.get());
+
+    assert!(tracker.is_pending());
+
+    resolve(&requests, 0, Ok("ada".to_string()));
+    executor.run_until_stalled();
+
+    assert!(!tracker.is_pending());
+
+    id.set(2);
+
+    assert!(tracker.is_pending());
+}
+
+#[test]
+#[should_panic(expected = "no executor has been set")]
+fn creating_a_resource_without_an_executor_panics() {
+    let id = create_state(1);
+    user_resource(&id);
+}
@@ -0,0 +1,119 @@
+use std::{cell::Cell, rc::Rc};
+
+use nestix_signal::{
+    ManualScheduler, Scheduler, State, batch, clear_scheduler, computed, create_state, effect,
+    flush_effects, set_scheduler,
+};
+
+fn count_runs(state: &State<i32>) -> Rc<Cell<i32>> {
+    let runs = Rc::new(Cell::new(0));
+    effect({
+        let state = state.clone();
+        let runs = runs.clone();
+        move || {
+            state.get();
+            runs.set(runs.get() + 1);
+        }
+    });
+    runs
+}
+
+#[test]
+fn writes_are_deferred_until_the_scheduler_flushes() {
+    let scheduler = ManualScheduler::new();
+    set_scheduler(scheduler.clone());
+    let count = create_state(0);
+    let doubled = computed({
+        let count = count.clone();
+        move || count.get() * 2
+    });
+    let runs = count_runs(&count);
+
+    assert!(!scheduler.is_scheduled());
+    count.set(1);
+    count.set(2);
+
+    // Computed values stay current while effects are pending.
+    assert_eq!(doubled.get(), 4);
+    assert_eq!(runs.get(), 1);
+    assert!(scheduler.is_scheduled());
+
+    scheduler.flush();
+    assert_eq!(runs.get(), 2);
+    assert!(!scheduler.is_scheduled());
+}
+
+#[test]
+fn writes_made_by_effects_are_handled_in_the_same_flush() {
+    let scheduler = ManualScheduler::new();
+    set_scheduler(scheduler.clone());
+    let count = create_state(0);
+    let copy = create_state(0);
+    effect({
+        let count = count.clone();
+        let copy = copy.clone();
+        move || copy.set(count.get())
+    });
+    let copy_runs = count_runs(&copy);
+
+    count.set(5);
+    scheduler.flush();
+
+    assert_eq!(copy.get(), 5);
+    assert_eq!(copy_runs.get(), 2);
+    assert!(!scheduler.is_scheduled());
+}
+
+#[test]
+fn schedule_is_requested_once_per_flush() {
+    struct CountingScheduler(Rc<Cell<i32>>);
+
+    impl Scheduler for CountingScheduler {
+        fn schedule(&self) {
+            self.0.set(self.0.get() + 1);
+        }
+    }
+
+    let requests = Rc::new(Cell::new(0));
+    set_scheduler(CountingScheduler(requests.clone()));
+    let count = create_state(0);
+    let runs = count_runs(&count);
+
+    count.set(1);
+    count.set(2);
+    assert_eq!(requests.get(), 1);
+
+    flush_effects();
+    assert_eq!(runs.get(), 2);
+
+    count.set(3);
+    assert_eq!(requests.get(), 2);
+
+    clear_scheduler();
+    assert_eq!(runs.get(), 3);
+
+    count.set(4);
+    assert_eq!(runs.get(), 4);
+    assert_eq!(requests.get(), 2);
+}
+
+#[test]
+fn flushing_inside_a_batch_schedules_again_when_the_batch_completes() {
+    let scheduler = ManualScheduler::new();
+    set_scheduler(scheduler.clone());
+    let count = create_state(0);
+    let runs = count_runs(&count);
+
+    count.set(1);
+    assert!(scheduler.is_scheduled());
+
+    batch(|| {
+        scheduler.flush();
+        assert_eq!(runs.get(), 1);
+        count.set(2);
+    });
+
+    assert!(scheduler.is_scheduled());
+    scheduler.flush();
+    assert_eq!(runs.get(), 2);
+}
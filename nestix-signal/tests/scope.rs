@@ -0,0 +1,231 @@
+use std::{cell::Cell, rc::Rc};
+
+use nestix_signal::{Scope, computed, create_root, create_state, current_scope, effect};
+
+fn counter() -> (Rc<Cell<usize>>, impl Fn() + Clone + 'static) {
+    let runs = Rc::new(Cell::new(0));
+    let increment = {
+        let runs = runs.clone();
+        move || runs.set(runs.get() + 1)
+    };
+    (runs, increment)
+}
+
+#[test]
+fn disposing_a_root_cancels_everything_it_owns() {
+    let count = create_state(1);
+    let (effect_runs, on_effect) = counter();
+    let (nested_runs, on_nested) = counter();
+    let (computed_runs, on_computed) = counter();
+
+    let (scope, nested) = create_root(|scope| {
+        effect({
+            let count = count.clone();
+            move || {
+                count.get();
+                on_effect();
+            }
+        });
+
+        let doubled = computed({
+            let count = count.clone();
+            move || count.get() * 2
+        });
+        effect(move || {
+            doubled.get();
+            on_computed();
+        });
+
+        let nested = Scope::new();
+        nested.effect({
+            let count = count.clone();
+            move || {
+                count.get();
+                on_nested();
+            }
+        });
+
+        (scope, nested)
+    });
+
+    count.set(2);
+    assert_eq!(effect_runs.get(), 2);
+    assert_eq!(nested_runs.get(), 2);
+    assert_eq!(computed_runs.get(), 2);
+
+    scope.dispose();
+    count.set(3);
+
+    assert!(scope.is_disposed());
+    assert!(nested.is_disposed());
+    assert_eq!(effect_runs.get(), 2);
+    assert_eq!(nested_runs.get(), 2);
+    assert_eq!(computed_runs.get(), 2);
+}
+
+#[test]
+fn nested_scopes_can_be_disposed_on_their_own() {
+    let count = create_state(1);
+    let (outer_runs, on_outer) = counter();
+    let (inner_runs, on_inner) = counter();
+
+    let (outer, inner) = create_root(|outer| {
+        effect({
+            let count = count.clone();
+            move || {
+                count.get();
+                on_outer();
+            }
+        });
+        let inner = Scope::new();
+        inner.run(|| {
+            effect({
+                let count = count.clone();
+                move || {
+                    count.get();
+                    on_inner();
+                }
+            });
+        });
+        (outer, inner)
+    });
+
+    inner.dispose();
+    count.set(2);
+
+    assert!(!outer.is_disposed());
+    assert_eq!(outer_runs.get(), 2);
+    assert_eq!(inner_runs.get(), 1);
+}
+
+#[test]
+fn effects_created_by_effects_belong_to_the_same_scope() {
+    let trigger = create_state(1);
+    let count = create_state(1);
+    let (inner_runs, on_inner) = counter();
+
+    let scope = create_root(|scope| {
+        effect({
+            let trigger = trigger.clone();
+            let count = count.clone();
+            move || {
+                trigger.get();
+                let count = count.clone();
+                let on_inner = on_inner.clone();
+                effect(move || {
+                    count.get();
+                    on_inner();
+                });
+            }
+        });
+        scope
+    });
+
+    trigger.set(2);
+    assert_eq!(inner_runs.get(), 2);
+
+    scope.dispose();
+    count.set(2);
+
+    assert_eq!(inner_runs.get(), 2);
+    assert!(current_scope().is_none());
+}
+
+#[test]
+fn create_root_is_not_owned_by_the_current_scope() {
+    let count = create_state(1);
+    let (runs, on_run) = counter();
+
+    let outer = create_root(|outer| {
+        create_root(|_| {
+            effect({
+                let count = count.clone();
+                move || {
+                    count.get();
+                    on_run();
+                }
+            });
+        });
+        outer
+    });
+
+    outer.dispose();
+    count.set(2);
+
+    assert_eq!(runs.get(), 2);
+}
+
+#[test]
+fn values_created_by_an_effect_are_disposed_before_it_reruns() {
+    let trigger = create_state(1);
+    let count = create_state(1);
+    let (inner_runs, on_inner) = counter();
+    let (computed_runs, on_computed) = counter();
+
+    let handle = effect({
+        let trigger = trigger.clone();
+        let count = count.clone();
+        move || {
+            trigger.get();
+            effect({
+                let count = count.clone();
+                let on_inner = on_inner.clone();
+                move || {
+                    count.get();
+                    on_inner();
+                }
+            });
+            let doubled = computed({
+                let count = count.clone();
+                let on_computed = on_computed.clone();
+                move || {
+                    on_computed();
+                    count.get() * 2
+                }
+            });
+            effect(move || {
+                doubled.get();
+            });
+        }
+    });
+
+    trigger.set(2);
+    assert_eq!((inner_runs.get(), computed_runs.get()), (2, 2));
+
+    // Only the effect and computed value of the latest run are still alive.
+    count.set(2);
+    assert_eq!((inner_runs.get(), computed_runs.get()), (3, 3));
+
+    handle.cancel();
+    count.set(3);
+    assert_eq!((inner_runs.get(), computed_runs.get()), (3, 3));
+}
+
+#[test]
+fn effects_created_by_a_computed_value_are_disposed_before_it_reevaluates() {
+    let trigger = create_state(1);
+    let count = create_state(1);
+    let (inner_runs, on_inner) = counter();
+
+    let value = computed({
+        let trigger = trigger.clone();
+        let count = count.clone();
+        move || {
+            let on_inner = on_inner.clone();
+            let count = count.clone();
+            effect(move || {
+                count.get();
+                on_inner();
+            });
+            trigger.get()
+        }
+    });
+
+    value.get();
+    trigger.set(2);
+    value.get();
+    assert_eq!(inner_runs.get(), 2);
+
+    count.set(2);
+    assert_eq!(inner_runs.get(), 3);
+}
@@ -0,0 +1,95 @@
+#![cfg(feature = "serde")]
+
+use std::{cell::Cell, collections::HashMap, rc::Rc};
+
+use nestix_signal::{
+    computed, create_state, effect, register_state, restore_states, snapshot_states,
+    unregister_state,
+};
+use serde_json::json;
+
+#[test]
+fn signals_serialize_their_current_values() {
+    let count = create_state(2);
+    let doubled = computed({
+        let count = count.clone();
+        move || count.get() * 2
+    });
+
+    assert_eq!(
+        serde_json::to_value((&count, &doubled, count.clone().into_readonly())).unwrap(),
+        json!([2, 4, 2])
+    );
+}
+
+#[test]
+fn snapshots_contain_live_registered_states() {
+    let name = create_state("Alice".to_string());
+    let tags = create_state(vec!["a".to_string()]);
+    let temporary = create_state(0);
+    register_state("name", &name);
+    register_state("tags", &tags);
+    register_state("temporary", &temporary);
+    drop(temporary);
+
+    assert_eq!(
+        snapshot_states().unwrap(),
+        json!({ "name": "Alice", "tags": ["a"] })
+    );
+
+    unregister_state("tags");
+    assert_eq!(snapshot_states().unwrap(), json!({ "name": "Alice" }));
+}
+
+#[test]
+fn restoring_writes_all_states_in_one_batch() {
+    let first = create_state(1);
+    let second = create_state(2);
+    register_state("first", &first);
+    register_state("second", &second);
+    let runs = Rc::new(Cell::new(0));
+
+    effect({
+        let first = first.clone();
+        let second = second.clone();
+        let runs = runs.clone();
+        move || {
+            first.get();
+            second.get();
+            runs.set(runs.get() + 1);
+        }
+    });
+
+    restore_states(&json!({ "first": 10, "second": 20, "unknown": true })).unwrap();
+
+    assert_eq!((first.get(), second.get()), (10, 20));
+    assert_eq!(runs.get(), 2);
+}
+
+#[test]
+fn failed_restores_leave_every_state_unchanged() {
+    let first = create_state(1);
+    let second = create_state(2);
+    register_state("first", &first);
+    register_state("second", &second);
+
+    let error = restore_states(&json!({ "first": 10, "second": "two" })).unwrap_err();
+
+    assert_eq!(error.name(), "second");
+    assert_eq!((first.get(), second.get()), (1, 2));
+}
+
+#[test]
+fn snapshots_name_the_state_that_fails_to_serialize() {
+    let valid = create_state(1);
+    // JSON objects need string keys.
+    let invalid = create_state(HashMap::from([((1, 2), "pair".to_string())]));
+    register_state("valid", &valid);
+    register_state("invalid", &invalid);
+
+    let error = snapshot_states().unwrap_err();
+
+    assert_eq!(error.name(), "invalid");
+    unregister_state("invalid");
+    assert_eq!(snapshot_states().unwrap(), json!({ "valid": 1 }));
+}
@@ -0,0 +1,98 @@
+#![cfg(feature = "sync")]
+
+use std::{cell::RefCell, rc::Rc, thread};
+
+use nestix_signal::{LocalExecutor, SyncState, create_state, effect, set_executor};
+
+fn assert_send_sync<T: Send + Sync>() {}
+
+#[test]
+fn sync_state_handles_are_send_and_sync() {
+    assert_send_sync::<SyncState<String>>();
+}
+
+#[test]
+fn writes_from_other_threads_apply_when_the_owner_runs_its_executor() {
+    let executor = LocalExecutor::new();
+    set_executor(executor.clone());
+    let count = create_state(0);
+    let observed = Rc::new(RefCell::new(Vec::new()));
+
+    effect({
+        let count = count.clone();
+        let observed = observed.clone();
+        move || observed.borrow_mut().push(count.get())
+    });
+
+    let handle = count.sync_handle();
+    thread::spawn(move || {
+        handle.set(1);
+        handle.update(|count| count + 1);
+    })
+    .join()
+    .unwrap();
+
+    assert_eq!(count.get(), 0);
+
+    executor.run_until_stalled();
+
+    assert_eq!(count.get(), 2);
+    assert_eq!(*observed.borrow(), vec![0, 2]);
+}
+
+#[test]
+fn writes_from_other_threads_apply_after_the_executor_is_replaced() {
+    let first = LocalExecutor::new();
+    set_executor(first.clone());
+    let count = create_state(0);
+    let handle = count.sync_handle();
+
+    let second = LocalExecutor::new();
+    set_executor(second.clone());
+    thread::spawn(move || handle.set(1)).join().unwrap();
+
+    first.run_until_stalled();
+    assert_eq!(count.get(), 0);
+
+    second.run_until_stalled();
+    assert_eq!(count.get(), 1);
+}
+
+#[test]
+fn writes_on_the_owning_thread_apply_immediately() {
+    set_executor(LocalExecutor::new());
+    let items = create_state(Vec::new());
+    let handle = items.sync_handle();
+
+    handle.mutate(|items| items.push("a"));
+
+    assert_eq!(items.get(), vec!["a"]);
+}
+
+#[test]
+fn writes_to_dropped_states_are_discarded() {
+    let executor = LocalExecutor::new();
+    set_executor(executor.clone());
+    let handle = create_state(0).sync_handle();
+
+    thread::spawn(move || handle.set(1)).join().unwrap();
+
+    executor.run_until_stalled();
+}
+
+#[test]
+fn dropping_a_handle_keeps_its_clones_working() {
+    let executor = LocalExecutor::new();
+    set_executor(executor.clone());
+    let count = create_state(0);
+    let handle = count.sync_handle();
+    let clone = handle.clone();
+
+    thread::spawn(move || drop(handle)).join().unwrap();
+    executor.run_until_stalled();
+
+    thread::spawn(move || clone.set(1)).join().unwrap();
+    executor.run_until_stalled();
+
+    assert_eq!(count.get(), 1);
+}
@@ -0,0 +1,121 @@
+use std::time::Duration;
+
+use nestix_signal::{
+    FakeClock, Scope, computed, create_state, debounced, interval, set_clock, throttled,
+};
+
+fn ms(millis: u64) -> Duration {
+    Duration::from_millis(millis)
+}
+
+#[test]
+fn debounced_takes_over_the_last_value_after_a_quiet_period() {
+    let clock = FakeClock::new();
+    set_clock(clock.clone());
+    let query = create_state(0);
+    let search = debounced(query.clone(), ms(100));
+
+    query.set(1);
+    clock.advance(ms(60));
+    query.set(2);
+    clock.advance(ms(60));
+    assert_eq!(search.get(), 0);
+    assert_eq!(clock.pending_timers(), 1);
+
+    clock.advance(ms(40));
+    assert_eq!(search.get(), 2);
+    assert_eq!(clock.pending_timers(), 0);
+}
+
+#[test]
+fn throttled_emits_leading_and_trailing_values() {
+    let clock = FakeClock::new();
+    set_clock(clock.clone());
+    let width = create_state(0);
+    let throttled_width = throttled(width.clone(), ms(100));
+
+    width.set(1);
+    assert_eq!(throttled_width.get(), 1);
+
+    width.set(2);
+    width.set(3);
+    clock.advance(ms(50));
+    assert_eq!(throttled_width.get(), 1);
+
+    clock.advance(ms(50));
+    assert_eq!(throttled_width.get(), 3);
+
+    // The trailing value started another wait.
+    width.set(4);
+    clock.advance(ms(99));
+    assert_eq!(throttled_width.get(), 3);
+    clock.advance(ms(1));
+    assert_eq!(throttled_width.get(), 4);
+
+    clock.advance(ms(100));
+    width.set(5);
+    assert_eq!(throttled_width.get(), 5);
+}
+
+#[test]
+fn interval_counts_elapsed_periods() {
+    let clock = FakeClock::new();
+    set_clock(clock.clone());
+    let ticks = interval(ms(10));
+
+    assert_eq!(ticks.get(), 0);
+    clock.advance(ms(35));
+    assert_eq!(ticks.get(), 3);
+    assert_eq!(clock.now(), ms(35));
+}
+
+#[test]
+fn timers_are_cleared_when_their_scope_is_disposed() {
+    let clock = FakeClock::new();
+    set_clock(clock.clone());
+    let source = create_state(0);
+    let scope = Scope::new();
+    let (ticks, delayed) = scope.run(|| (interval(ms(10)), debounced(source.clone(), ms(10))));
+
+    source.set(1);
+    assert_eq!(clock.pending_timers(), 2);
+
+    scope.dispose();
+    assert_eq!(clock.pending_timers(), 0);
+
+    source.set(2);
+    clock.advance(ms(100));
+    assert_eq!((ticks.get(), delayed.get()), (0, 0));
+    assert_eq!(clock.pending_timers(), 0);
+}
+
+#[test]
+fn timers_are_not_cleared_when_the_computed_value_creating_them_is_invalidated() {
+    let clock = FakeClock::new();
+    set_clock(clock.clone());
+    let trigger = create_state(0);
+    let scope = Scope::new();
+    let ticks = scope.run(|| {
+        computed({
+            let trigger = trigger.clone();
+            move || {
+                trigger.get();
+                interval(ms(10))
+            }
+        })
+    });
+
+    let first = ticks.get();
+    trigger.set(1);
+    clock.advance(ms(10));
+    assert_eq!(first.get(), 1);
+
+    // Evaluating again clears the timers of the previous evaluation.
+    let second = ticks.get();
+    clock.advance(ms(10));
+    assert_eq!((first.get(), second.get()), (1, 1));
+    assert_eq!(clock.pending_timers(), 1);
+
+    scope.dispose();
+    assert_eq!(clock.pending_timers(), 0);
+}
@@ -0,0 +1,70 @@
+use std::collections::BTreeMap;
+
+use nestix::{Element, Fragment, Layout, component, effect, layout, props};
+
+use crate::{TestNode, TestNodeDesc, TestRenderer};
+
+/// Props for [`Node`].
+#[props]
+pub struct NodeProps {
+    /// Tag name of the created node.
+    #[props(start)]
+    tag: String,
+    /// Attributes of the created node.
+    #[props(default)]
+    attributes: BTreeMap<String, String>,
+    /// Child layout placed inside the created node.
+    #[props(default)]
+    children: Layout,
+}
+
+/// Host component that creates a tag node in the in-memory test tree.
+#[component]
+pub fn Node(props: &NodeProps, element: &Element) -> Element {
+    let node = element.create_host_node::<TestRenderer>(TestNodeDesc::Tag(props.tag.get()));
+
+    effect!([node, props.attributes] || node.set_attributes(attributes.get()));
+
+    layout! {
+        Fragment {
+            $(props.children.clone())
+        }
+    }
+}
+
+/// Props for [`Text`].
+#[props]
+pub struct TextProps {
+    /// Content of the created text node.
+    #[props(start)]
+    text: String,
+}
+
+/// Host component that creates a text node in the in-memory test tree.
+#[component]
+pub fn Text(props: &TextProps, element: &Element) {
+    let node = element.create_host_node::<TestRenderer>(TestNodeDesc::Text(props.text.get()));
+
+    effect!([node, props.text] || node.set_text(text.get()));
+}
+
+#[props]
+pub(crate) struct ContainerProps {
+    #[props(raw)]
+    pub(crate) node: TestNode,
+    #[props(default)]
+    pub(crate) children: Layout,
+}
+
+/// Root host component that exposes an existing node as the host parent of its
+/// children.
+#[component]
+pub(crate) fn Container(props: &ContainerProps, element: &Element) -> Element {
+    element.provide_handle(props.node.clone());
+
+    layout! {
+        Fragment {
+            $(props.children.clone())
+        }
+    }
+}
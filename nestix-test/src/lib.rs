@@ -0,0 +1,31 @@
+//! Headless in-memory host for testing Nestix components.
+//!
+//! The [`Node`] and [`Text`] host components create nodes in an in-memory tree
+//! through [`TestRenderer`], which keeps it in sync with element placement and
+//! unmounting, so component behavior can be asserted without a real rendering
+//! backend.
+//!
+//! ```
+//! use nestix::layout;
+//! use nestix_test::{Node, Text, render};
+//!
+//! let root = render(layout! {
+//!     Node("list") {
+//!         Text("first")
+//!         Text("second")
+//!     }
+//! });
+//!
+//! assert_eq!(root.snapshot(), "<list>\n  \"first\"\n  \"second\"\n");
+//! assert!(root.find_by_text("second").is_some());
+//! ```
+
+mod components;
+mod node;
+mod render;
+mod renderer;
+
+pub use components::{Node, NodeProps, Text, TextProps};
+pub use node::*;
+pub use render::*;
+pub use renderer::*;
@@ -0,0 +1,243 @@
+use std::{
+    cell::RefCell,
+    collections::BTreeMap,
+    fmt::{Debug, Write},
+    rc::{Rc, Weak},
+};
+
+#[derive(Debug)]
+enum NodeKind {
+    Tag(String),
+    Text,
+}
+
+#[derive(Debug)]
+struct NodeData {
+    kind: NodeKind,
+    text: RefCell<String>,
+    attributes: RefCell<BTreeMap<String, String>>,
+    parent: RefCell<Weak<NodeData>>,
+    children: RefCell<Vec<TestNode>>,
+}
+
+/// A node in the in-memory host tree built by the test host components.
+///
+/// `TestNode` compares by identity. Cloning it produces another handle to the
+/// same node.
+#[derive(Clone)]
+pub struct TestNode {
+    data: Rc<NodeData>,
+}
+
+impl TestNode {
+    /// Creates a detached tag node.
+    pub fn tag_node(tag: impl Into<String>) -> Self {
+        Self::new(NodeKind::Tag(tag.into()), String::new())
+    }
+
+    /// Creates a detached text node.
+    pub fn text_node(text: impl Into<String>) -> Self {
+        Self::new(NodeKind::Text, text.into())
+    }
+
+    fn new(kind: NodeKind, text: String) -> Self {
+        Self {
+            data: Rc::new(NodeData {
+                kind,
+                text: RefCell::new(text),
+                attributes: RefCell::new(BTreeMap::new()),
+                parent: RefCell::new(Weak::new()),
+                children: RefCell::new(Vec::new()),
+            }),
+        }
+    }
+
+    /// Returns the tag name, or `None` for text nodes.
+    pub fn tag(&self) -> Option<&str> {
+        match &self.data.kind {
+            NodeKind::Tag(tag) => Some(tag),
+            NodeKind::Text => None,
+        }
+    }
+
+    /// Returns whether this is a text node.
+    pub fn is_text(&self) -> bool {
+        matches!(self.data.kind, NodeKind::Text)
+    }
+
+    /// Returns the text of a text node, or `None` for tag nodes.
+    pub fn text(&self) -> Option<String> {
+        self.is_text().then(|| self.data.text.borrow().clone())
+    }
+
+    /// Returns the concatenated text of all text nodes in this subtree.
+    pub fn text_content(&self) -> String {
+        if self.is_text() {
+            return self.data.text.borrow().clone();
+        }
+
+        self.children().iter().map(TestNode::text_content).collect()
+    }
+
+    /// Replaces the text of a text node.
+    pub fn set_text(&self, text: impl Into<String>) {
+        self.data.text.replace(text.into());
+    }
+
+    /// Returns the value of attribute `name`, if it is set.
+    pub fn attribute(&self, name: &str) -> Option<String> {
+        self.data.attributes.borrow().get(name).cloned()
+    }
+
+    /// Returns a snapshot of all attributes, ordered by name.
+    pub fn attributes(&self) -> BTreeMap<String, String> {
+        self.data.attributes.borrow().clone()
+    }
+
+    /// Replaces all attributes of this node.
+    pub fn set_attributes(&self, attributes: BTreeMap<String, String>) {
+        self.data.attributes.replace(attributes);
+    }
+
+    /// Returns this node's parent, if it is attached.
+    pub fn parent(&self) -> Option<TestNode> {
+        self.data
+            .parent
+            .borrow()
+            .upgrade()
+            .map(|data| TestNode { data })
+    }
+
+    /// Returns a snapshot of this node's children in order.
+    pub fn children(&self) -> Vec<TestNode> {
+        self.data.children.borrow().clone()
+    }
+
+    /// Returns this node's index among its parent's children.
+    pub fn index(&self) -> Option<usize> {
+        let parent = self.parent()?;
+        let children = parent.data.children.borrow();
+        children.iter().position(|child| child == self)
+    }
+
+    /// Returns the first node in this subtree, in document order, with tag
+    /// `tag`. The node itself is included in the search.
+    pub fn find_by_tag(&self, tag: &str) -> Option<TestNode> {
+        self.find(|node| node.tag() == Some(tag))
+    }
+
+    /// Returns all nodes in this subtree, in document order, with tag `tag`.
+    pub fn find_all_by_tag(&self, tag: &str) -> Vec<TestNode> {
+        self.find_all(|node| node.tag() == Some(tag))
+    }
+
+    /// Returns the first text node in this subtree whose text equals `text`.
+    pub fn find_by_text(&self, text: &str) -> Option<TestNode> {
+        self.find(|node| node.is_text() && *node.data.text.borrow() == text)
+    }
+
+    /// Returns the first node in this subtree, in document order, matching
+    /// `predicate`.
+    pub fn find(&self, predicate: impl Fn(&TestNode) -> bool) -> Option<TestNode> {
+        self.find_all(predicate).into_iter().next()
+    }
+
+    /// Returns all nodes in this subtree, in document order, matching
+    /// `predicate`.
+    pub fn find_all(&self, predicate: impl Fn(&TestNode) -> bool) -> Vec<TestNode> {
+        let mut found = Vec::new();
+        self.collect(&predicate, &mut found);
+        found
+    }
+
+    fn collect(&self, predicate: &impl Fn(&TestNode) -> bool, found: &mut Vec<TestNode>) {
+        if predicate(self) {
+            found.push(self.clone());
+        }
+        for child in self.children() {
+            child.collect(predicate, found);
+        }
+    }
+
+    /// Renders this node's children as an indented, line-based snapshot.
+    ///
+    /// Tag nodes are written as `<tag name="value">` with their children
+    /// indented below them, and text nodes as quoted strings.
+    pub fn snapshot(&self) -> String {
+        let mut output = String::new();
+        for child in self.children() {
+            child.write_snapshot(&mut output, 0);
+        }
+        output
+    }
+
+    fn write_snapshot(&self, output: &mut String, depth: usize) {
+        let indent = "  ".repeat(depth);
+        match &self.data.kind {
+            NodeKind::Text => {
+                writeln!(output, "{indent}{:?}", self.data.text.borrow()).unwrap();
+            }
+            NodeKind::Tag(tag) => {
+                write!(output, "{indent}<{tag}").unwrap();
+                for (name, value) in self.data.attributes.borrow().iter() {
+                    write!(output, " {name}={value:?}").unwrap();
+                }
+                writeln!(output, ">").unwrap();
+                for child in self.children() {
+                    child.write_snapshot(output, depth + 1);
+                }
+            }
+        }
+    }
+
+    /// Inserts `node` as a child of this node directly after `pred`, or as the
+    /// first child when `pred` is `None` or not a child of this node.
+    ///
+    /// `node` is detached from its current parent first, so this also moves
+    /// nodes that are already attached.
+    pub fn insert_after(&self, pred: Option<&TestNode>, node: &TestNode) {
+        node.detach();
+        let mut children = self.data.children.borrow_mut();
+        let index = pred
+            .and_then(|pred| children.iter().position(|child| child == pred))
+            .map_or(0, |index| index + 1);
+        children.insert(index, node.clone());
+        node.data.parent.replace(Rc::downgrade(&self.data));
+    }
+
+    /// Appends `node` as the last child of this node.
+    pub fn append(&self, node: &TestNode) {
+        node.detach();
+        self.data.children.borrow_mut().push(node.clone());
+        node.data.parent.replace(Rc::downgrade(&self.data));
+    }
+
+    /// Removes this node from its parent, if it has one.
+    pub fn detach(&self) {
+        if let Some(parent) = self.parent() {
+            parent
+                .data
+                .children
+                .borrow_mut()
+                .retain(|child| child != self);
+        }
+        self.data.parent.replace(Weak::new());
+    }
+}
+
+impl PartialEq for TestNode {
+    fn eq(&self, other: &Self) -> bool {
+        Rc::ptr_eq(&self.data, &other.data)
+    }
+}
+
+impl Eq for TestNode {}
+
+impl Debug for TestNode {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match &self.data.kind {
+            NodeKind::Tag(tag) => write!(f, "TestNode(<{tag}>)"),
+            NodeKind::Text => write!(f, "TestNode({:?})", self.data.text.borrow()),
+        }
+    }
+}
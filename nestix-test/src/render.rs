@@ -0,0 +1,70 @@
+use nestix::{Element, Layout, Root, build_props, create_element, mount_root_with_renderer};
+
+use crate::{
+    TestNode, TestRenderer,
+    components::{Container, ContainerProps},
+};
+
+/// A layout mounted into an in-memory host tree by [`render`].
+pub struct TestRoot {
+    container: TestNode,
+    root: Root,
+}
+
+impl TestRoot {
+    /// Returns the container node that holds the rendered host nodes.
+    pub fn container(&self) -> &TestNode {
+        &self.container
+    }
+
+    /// Returns the mounted root element.
+    pub fn element(&self) -> &Element {
+        self.root.element()
+    }
+
+    /// Returns the first rendered node with tag `tag`.
+    pub fn find_by_tag(&self, tag: &str) -> Option<TestNode> {
+        self.container.find_by_tag(tag)
+    }
+
+    /// Returns all rendered nodes with tag `tag`.
+    pub fn find_all_by_tag(&self, tag: &str) -> Vec<TestNode> {
+        self.container.find_all_by_tag(tag)
+    }
+
+    /// Returns the first rendered text node whose text equals `text`.
+    pub fn find_by_text(&self, text: &str) -> Option<TestNode> {
+        self.container.find_by_text(text)
+    }
+
+    /// Renders the current host tree as a string. See [`TestNode::snapshot`].
+    pub fn snapshot(&self) -> String {
+        self.container.snapshot()
+    }
+
+    /// Returns whether the rendered layout is still mounted.
+    pub fn is_mounted(&self) -> bool {
+        self.root.is_mounted()
+    }
+
+    /// Unmounts the rendered layout, removing its nodes from the container.
+    pub fn unmount(&self) {
+        self.root.unmount();
+    }
+}
+
+/// Mounts `layout` under a fresh container node and returns the mounted root.
+///
+/// Host nodes are created by [`Node`](crate::Node) and
+/// [`Text`](crate::Text) elements in the layout; other components only
+/// contribute their descendants.
+pub fn render(layout: impl Into<Layout>) -> TestRoot {
+    let container = TestNode::tag_node("root");
+    let element = create_element::<Container>(build_props!(ContainerProps(
+        .node = container.clone(),
+        .children = layout.into(),
+    )));
+    let root = mount_root_with_renderer(&element, TestRenderer);
+
+    TestRoot { container, root }
+}
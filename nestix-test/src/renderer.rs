@@ -0,0 +1,39 @@
+use nestix::Renderer;
+
+use crate::TestNode;
+
+/// Describes a node created by [`TestRenderer`].
+#[derive(Debug, Clone)]
+pub enum TestNodeDesc {
+    /// A tag node with the given tag name.
+    Tag(String),
+    /// A text node with the given text.
+    Text(String),
+}
+
+/// A [`Renderer`] that arranges [`TestNode`]s in memory.
+pub struct TestRenderer;
+
+impl Renderer for TestRenderer {
+    type Node = TestNode;
+    type NodeDesc = TestNodeDesc;
+
+    fn create_node(&self, desc: TestNodeDesc) -> TestNode {
+        match desc {
+            TestNodeDesc::Tag(tag) => TestNode::tag_node(tag),
+            TestNodeDesc::Text(text) => TestNode::text_node(text),
+        }
+    }
+
+    fn insert_after(&self, parent: &TestNode, pred: &TestNode, node: &TestNode) {
+        parent.insert_after(Some(pred), node);
+    }
+
+    fn prepend(&self, parent: &TestNode, node: &TestNode) {
+        parent.insert_after(None, node);
+    }
+
+    fn remove(&self, node: &TestNode) {
+        node.detach();
+    }
+}
@@ -0,0 +1,133 @@
+use std::rc::Rc;
+
+use nestix::{
+    ContextError, ContextKey, ContextProvider, Element, Fragment, Layout, component, computed,
+    create_state, layout, props, use_context,
+};
+use nestix_test::{Node, Text, render};
+
+#[derive(Debug, PartialEq)]
+struct Theme(&'static str);
+
+#[component]
+fn ThemeName(_: &(), element: &Element) -> Element {
+    layout! {
+        Text(computed!([element] || {
+            element.context::<Theme>().map_or("none", |theme| theme.0).to_string()
+        }))
+    }
+}
+
+#[test]
+fn context_changes_reach_mounted_descendants() {
+    let theme = create_state(Rc::new(Theme("light")));
+    let root = render(layout! {
+        Node("app") {
+            ContextProvider<Theme>(theme.clone()) {
+                Node("panel") {
+                    ThemeName
+                }
+            }
+        }
+    });
+
+    assert_eq!(root.snapshot(), "<app>\n  <panel>\n    \"light\"\n");
+
+    theme.set(Rc::new(Theme("dark")));
+    assert_eq!(root.snapshot(), "<app>\n  <panel>\n    \"dark\"\n");
+}
+
+#[test]
+fn context_resolves_to_the_nearest_provider() {
+    let outer = create_state(Rc::new(Theme("outer")));
+    let inner = create_state(Rc::new(Theme("inner")));
+    let root = render(layout! {
+        Node("app") {
+            ThemeName
+            ContextProvider<Theme>(outer.clone()) {
+                ThemeName
+                ContextProvider<Theme>(inner.clone()) {
+                    ThemeName
+                }
+            }
+        }
+    });
+
+    assert_eq!(
+        root.snapshot(),
+        "<app>\n  \"none\"\n  \"outer\"\n  \"inner\"\n"
+    );
+
+    outer.set(Rc::new(Theme("other")));
+    assert_eq!(
+        root.snapshot(),
+        "<app>\n  \"none\"\n  \"other\"\n  \"inner\"\n"
+    );
+}
+
+thread_local! {
+    static ACCENT: ContextKey<String> = ContextKey::new("accent");
+    static BORDER: ContextKey<String> = ContextKey::new("border");
+}
+
+#[props]
+struct ColorNameProps {
+    #[props(start)]
+    key: ContextKey<String>,
+}
+
+#[component]
+fn ColorName(props: &ColorNameProps) -> Element {
+    let text = match use_context(&props.key.get()) {
+        Ok(color) => color.to_string(),
+        Err(error) => error.to_string(),
+    };
+    layout! {
+        Text(text)
+    }
+}
+
+#[props]
+struct BorderProps {
+    #[props(start)]
+    color: &'static str,
+    children: Layout,
+}
+
+#[component]
+fn Border(props: &BorderProps, element: &Element) -> Element {
+    element.provide_keyed_context(&BORDER.with(|key| *key), props.color.get().to_string());
+    layout! {
+        Fragment(.children = props.children.clone())
+    }
+}
+
+#[test]
+fn keyed_contexts_of_the_same_type_coexist() {
+    let accent = ACCENT.with(|key| *key);
+    let border = BORDER.with(|key| *key);
+    let root = render(layout! {
+        Node("app") {
+            ContextProvider<String>(Rc::new("red".to_string()), .key = Some(accent)) {
+                Border("blue") {
+                    ColorName(accent)
+                    ColorName(border)
+                }
+            }
+            ColorName(border)
+        }
+    });
+
+    assert_eq!(
+        root.snapshot(),
+        "<app>\n  \"red\"\n  \"blue\"\n  \"no provider for context `border`\"\n"
+    );
+}
+
+#[test]
+fn use_context_outside_a_component_reports_the_key() {
+    let error = use_context(&ACCENT.with(|key| *key)).unwrap_err();
+
+    assert_eq!(error, ContextError::OutsideComponent("accent"));
+    assert_eq!(error.name(), "accent");
+}
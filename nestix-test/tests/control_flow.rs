@@ -0,0 +1,139 @@
+use std::{cell::Cell, rc::Rc};
+
+use nestix::{
+    Element, Match, Show, ShowProps, Switch, SwitchProps, build_props, component, computed,
+    create_element, create_state, layout, props,
+};
+use nestix_test::{Node, Text, render};
+
+#[props]
+struct LabelProps {
+    #[props(start)]
+    mounts: Rc<Cell<usize>>,
+    #[props(start)]
+    text: String,
+}
+
+#[component]
+fn Label(props: &LabelProps) -> Element {
+    let mounts = props.mounts.get();
+    mounts.set(mounts.get() + 1);
+    layout! {
+        Text(props.text.clone())
+    }
+}
+
+#[test]
+fn show_remounts_only_when_the_condition_changes() {
+    let count = create_state(1);
+    let text = create_state("one".to_string());
+    let mounts = Rc::new(Cell::new(0));
+    let root = render(layout! {
+        Node("app") {
+            Show(
+                .when = computed!([count] || count.get() > 0),
+                .fallback = layout! { Text("empty") },
+            ) {
+                Label(mounts.clone(), text.clone())
+            }
+        }
+    });
+
+    assert_eq!(root.snapshot(), "<app>\n  \"one\"\n");
+
+    count.set(2);
+    text.set("two".to_string());
+    assert_eq!(root.snapshot(), "<app>\n  \"two\"\n");
+    assert_eq!(mounts.get(), 1);
+
+    count.set(0);
+    assert_eq!(root.snapshot(), "<app>\n  \"empty\"\n");
+
+    count.set(3);
+    text.set("three".to_string());
+    assert_eq!(root.snapshot(), "<app>\n  \"three\"\n");
+    assert_eq!(mounts.get(), 2);
+}
+
+#[test]
+fn switch_renders_the_first_matching_branch() {
+    let value = create_state(5);
+    let mounts = Rc::new(Cell::new(0));
+    let root = render(layout! {
+        Node("app") {
+            Switch(.fallback = layout! { Text("small") }) {
+                Match(.when = computed!([value] || value.get() > 100)) {
+                    Text("huge")
+                }
+                Match(.when = computed!([value] || value.get() > 10)) {
+                    Label(mounts.clone(), "large")
+                }
+            }
+        }
+    });
+
+    assert_eq!(root.snapshot(), "<app>\n  \"small\"\n");
+
+    value.set(20);
+    value.set(30);
+    assert_eq!(root.snapshot(), "<app>\n  \"large\"\n");
+    assert_eq!(mounts.get(), 1);
+
+    value.set(200);
+    assert_eq!(root.snapshot(), "<app>\n  \"huge\"\n");
+
+    value.set(50);
+    assert_eq!(root.snapshot(), "<app>\n  \"large\"\n");
+    assert_eq!(mounts.get(), 2);
+
+    value.set(0);
+    assert_eq!(root.snapshot(), "<app>\n  \"small\"\n");
+}
+
+#[test]
+fn show_can_be_created_as_a_plain_element() {
+    let visible = create_state(false);
+    let show = create_element::<Show>(build_props!(ShowProps(
+        .when = visible.clone(),
+        .children = layout! { Text("visible") },
+    )));
+    let root = render(show);
+
+    assert_eq!(root.snapshot(), "");
+
+    visible.set(true);
+    assert_eq!(root.snapshot(), "\"visible\"\n");
+}
+
+#[test]
+fn show_remounts_when_the_shown_layout_is_replaced() {
+    let label = create_state("first".to_string());
+    let show = create_element::<Show>(build_props!(ShowProps(
+        .when = true,
+        .children = computed!([label] || layout! { Text(label.get()) }),
+    )));
+    let root = render(show);
+
+    assert_eq!(root.snapshot(), "\"first\"\n");
+
+    label.set("second".to_string());
+    assert_eq!(root.snapshot(), "\"second\"\n");
+}
+
+#[test]
+fn switch_remounts_when_a_new_match_takes_the_same_place() {
+    let label = create_state("first".to_string());
+    let switch = create_element::<Switch>(build_props!(SwitchProps(
+        .children = computed!([label] || layout! {
+            Match(.when = true) {
+                Text(label.get())
+            }
+        }),
+    )));
+    let root = render(switch);
+
+    assert_eq!(root.snapshot(), "\"first\"\n");
+
+    label.set("second".to_string());
+    assert_eq!(root.snapshot(), "\"second\"\n");
+}
@@ -0,0 +1,72 @@
+use std::{cell::Cell, rc::Rc};
+
+use nestix::{
+    ComponentID, Dynamic, DynamicComponent, Element, Props, build_props, component, component_id,
+    create_element_with_id, create_state, layout, props,
+};
+use nestix_test::{Node, Text, TextProps, render};
+
+#[props]
+#[derive(Clone)]
+struct BadgeProps {
+    #[props(start)]
+    mounts: Rc<Cell<usize>>,
+}
+
+#[component]
+fn Badge(props: &BadgeProps) -> Element {
+    let mounts = props.mounts.get();
+    mounts.set(mounts.get() + 1);
+    layout! {
+        Node("badge")
+    }
+}
+
+#[test]
+fn dynamic_remounts_when_the_component_changes() {
+    let mounts = Rc::new(Cell::new(0));
+    let badge = DynamicComponent::of::<Badge>(build_props!(BadgeProps(mounts.clone())));
+    let widget = create_state(badge.clone());
+    let root = render(layout! {
+        Node("app") {
+            Dynamic(widget.clone())
+        }
+    });
+
+    assert_eq!(root.snapshot(), "<app>\n  <badge>\n");
+
+    widget.set_unchecked(badge);
+    assert_eq!(mounts.get(), 1);
+
+    widget.set(DynamicComponent::new(|| layout! { Text("plugin") }));
+    assert_eq!(root.snapshot(), "<app>\n  \"plugin\"\n");
+
+    widget.set(DynamicComponent::of::<Badge>(build_props!(BadgeProps(
+        mounts.clone()
+    ))));
+    assert_eq!(root.snapshot(), "<app>\n  <badge>\n");
+    assert_eq!(mounts.get(), 2);
+}
+
+fn text_widget(id: ComponentID, text: &'static str) -> DynamicComponent {
+    DynamicComponent::new(move || {
+        let props: Box<dyn Props> = Box::new(build_props!(TextProps(text)));
+        create_element_with_id(id, props)
+    })
+}
+
+#[test]
+fn dynamic_renders_components_from_their_id() {
+    let root = render(layout! {
+        Dynamic(text_widget(component_id::<Text>(), "by id"))
+    });
+
+    assert_eq!(root.snapshot(), "\"by id\"\n");
+}
+
+#[test]
+#[should_panic(expected = "do not belong to component")]
+fn create_element_with_id_rejects_mismatched_props() {
+    let props: Box<dyn Props> = Box::new(build_props!(TextProps("text")));
+    create_element_with_id(component_id::<Node>(), props);
+}
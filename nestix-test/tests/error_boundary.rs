@@ -0,0 +1,110 @@
+use nestix::{
+    CaughtPanic, Element, ErrorBoundary, ErrorBoundaryFallback, Shared, State, callback, component,
+    create_state, layout, props, scoped_effect,
+};
+use nestix_test::{Node, Text, render};
+
+#[component]
+fn PanicOnMount() {
+    panic!("mount failed");
+}
+
+#[props]
+struct PanicAtProps {
+    value: i32,
+}
+
+#[component]
+fn PanicAt(props: &PanicAtProps) -> Element {
+    scoped_effect!(
+        [props.value] || {
+            if value.get() == 2 {
+                panic!("value is 2");
+            }
+        }
+    );
+
+    layout! {
+        Text(format!("value {}", props.value.get()))
+    }
+}
+
+fn message_fallback() -> ErrorBoundaryFallback {
+    callback!(|error: CaughtPanic, _reset: Shared<dyn Fn()>| {
+        layout! {
+            Text(format!("error: {}", error.message()))
+        }
+    })
+}
+
+#[test]
+fn panic_during_mount_renders_fallback() {
+    let root = render(layout! {
+        Node("app") {
+            ErrorBoundary(.fallback = message_fallback()) {
+                Text("before")
+                PanicOnMount
+            }
+            Text("sibling")
+        }
+    });
+
+    assert_eq!(
+        root.snapshot(),
+        "<app>\n  \"error: mount failed\"\n  \"sibling\"\n"
+    );
+}
+
+#[test]
+fn panic_in_effect_rerun_unmounts_children_and_reset_restores_them() {
+    let value = create_state(1);
+    let reset: State<Option<Shared<dyn Fn()>>> = create_state(None);
+    let reset_in_fallback = reset.clone();
+
+    let root = render(layout! {
+        Node("app") {
+            ErrorBoundary(
+                .fallback = callback!(
+                    [reset_in_fallback] |error: CaughtPanic, reset: Shared<dyn Fn()>| {
+                        reset_in_fallback.set_unchecked(Some(reset));
+                        layout! {
+                            Text(error.message().to_string())
+                        }
+                    }
+                ),
+            ) {
+                PanicAt(.value = value.clone())
+            }
+        }
+    });
+
+    assert_eq!(root.snapshot(), "<app>\n  \"value 1\"\n");
+
+    value.set(2);
+
+    assert_eq!(root.snapshot(), "<app>\n  \"value is 2\"\n");
+
+    value.set(3);
+    assert_eq!(root.snapshot(), "<app>\n  \"value is 2\"\n");
+
+    (reset.get().unwrap())();
+
+    assert_eq!(root.snapshot(), "<app>\n  \"value 3\"\n");
+}
+
+#[test]
+fn panics_in_fallback_reach_the_enclosing_boundary() {
+    let root = render(layout! {
+        ErrorBoundary(.fallback = message_fallback()) {
+            ErrorBoundary(
+                .fallback = callback!(|_: CaughtPanic, _: Shared<dyn Fn()>| -> Element {
+                    panic!("fallback failed")
+                }),
+            ) {
+                PanicOnMount
+            }
+        }
+    });
+
+    assert_eq!(root.snapshot(), "\"error: fallback failed\"\n");
+}
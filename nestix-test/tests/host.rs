@@ -0,0 +1,150 @@
+use std::collections::BTreeMap;
+
+use nestix::{Fragment, create_state, layout};
+use nestix_test::{Node, Text, render};
+
+#[test]
+fn render_builds_host_tree_in_layout_order() {
+    let root = render(layout! {
+        Node("list", .attributes = [("id".to_string(), "items".to_string())]) {
+            Node("item") {
+                Text("first")
+            }
+            Node("item") {
+                Text("second")
+            }
+        }
+    });
+
+    assert_eq!(
+        root.snapshot(),
+        concat!(
+            "<list id=\"items\">\n",
+            "  <item>\n",
+            "    \"first\"\n",
+            "  <item>\n",
+            "    \"second\"\n",
+        )
+    );
+
+    let items = root.find_all_by_tag("item");
+    assert_eq!(items.len(), 2);
+    assert_eq!(items[1].text_content(), "second");
+    assert_eq!(items[1].index(), Some(1));
+    assert_eq!(
+        root.find_by_tag("list").unwrap().attribute("id").as_deref(),
+        Some("items")
+    );
+}
+
+#[test]
+fn host_nodes_follow_signal_changes() {
+    let label = create_state("ready".to_string());
+    let attributes = create_state(BTreeMap::new());
+    let root = render(layout! {
+        Node("label", .attributes = attributes.clone()) {
+            Text(label.clone())
+        }
+    });
+
+    label.set("done".to_string());
+    attributes.set(BTreeMap::from([("state".to_string(), "done".to_string())]));
+
+    assert_eq!(root.snapshot(), "<label state=\"done\">\n  \"done\"\n");
+}
+
+#[test]
+fn conditional_children_are_inserted_at_their_logical_position() {
+    let show = create_state(false);
+    let show_in_layout = show.clone();
+    let root = render(layout! {
+        Node("box") {
+            if show_in_layout.get() {
+                Text("first")
+            }
+            Text("last")
+        }
+    });
+
+    assert_eq!(root.snapshot(), "<box>\n  \"last\"\n");
+
+    show.set(true);
+    assert_eq!(root.snapshot(), "<box>\n  \"first\"\n  \"last\"\n");
+
+    show.set(false);
+    assert_eq!(root.snapshot(), "<box>\n  \"last\"\n");
+}
+
+#[test]
+fn for_moves_existing_nodes_when_items_are_reordered() {
+    let items = create_state(vec![1, 2, 3]);
+    let root = render(layout! {
+        Node("list") {
+            for item in items.clone() {
+                Text(item.get().to_string())
+            }
+        }
+    });
+
+    let third = root.find_by_text("3").unwrap();
+    items.set(vec![3, 1]);
+
+    assert_eq!(root.snapshot(), "<list>\n  \"3\"\n  \"1\"\n");
+    assert_eq!(root.find_by_text("3"), Some(third));
+    assert!(root.find_by_text("2").is_none());
+}
+
+#[test]
+fn unmount_removes_rendered_nodes() {
+    let root = render(layout! {
+        Node("list") {
+            Text("item")
+        }
+    });
+    let text = root.find_by_text("item").unwrap();
+
+    root.unmount();
+
+    assert!(root.container().children().is_empty());
+    assert!(text.parent().is_none());
+}
+
+#[test]
+fn rendered_roots_are_independent() {
+    let main = render(layout! {
+        Text("main")
+    });
+    let popup = render(layout! {
+        Text("popup")
+    });
+
+    popup.unmount();
+
+    assert!(!popup.is_mounted());
+    assert!(popup.container().children().is_empty());
+    assert!(main.is_mounted());
+    assert_eq!(main.snapshot(), "\"main\"\n");
+}
+
+#[test]
+fn nested_fragments_are_placed_after_preceding_siblings() {
+    let show = create_state(false);
+    let show_in_layout = show.clone();
+    let root = render(layout! {
+        Node("box") {
+            Text("a")
+            Fragment {
+                Text("b")
+            }
+            if show_in_layout.get() {
+                Text("c")
+            }
+        }
+    });
+
+    assert_eq!(root.snapshot(), "<box>\n  \"a\"\n  \"b\"\n");
+
+    show.set(true);
+
+    assert_eq!(root.snapshot(), "<box>\n  \"a\"\n  \"b\"\n  \"c\"\n");
+}
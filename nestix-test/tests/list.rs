@@ -0,0 +1,119 @@
+use std::{cell::Cell, fmt::Display, rc::Rc};
+
+use nestix::{
+    Element, Readonly, State, computed, create_state, create_state_map, create_state_vec, effect,
+    layout,
+};
+use nestix_test::{Node, Text, render};
+
+#[test]
+fn for_applies_state_vec_changes_without_recreating_items() {
+    let items = create_state_vec(vec!["a".to_string(), "b".to_string()]);
+    let renders = Rc::new(Cell::new(0));
+    let counter = renders.clone();
+    let root = render(layout! {
+        Node("list") {
+            for item in items.clone() {
+                $({
+                    counter.set(counter.get() + 1);
+                    layout! { Text(item) }
+                })
+            }
+        }
+    });
+    let b = root.find_by_text("b").unwrap();
+
+    items.insert(0, "c".to_string());
+    items.push("d".to_string());
+    items.move_item(3, 1);
+    items.remove(2);
+
+    assert_eq!(root.snapshot(), "<list>\n  \"c\"\n  \"d\"\n  \"b\"\n");
+    assert_eq!(root.find_by_text("b"), Some(b.clone()));
+    assert_eq!(renders.get(), 4);
+
+    items.set(0, "e".to_string());
+    assert_eq!(root.snapshot(), "<list>\n  \"e\"\n  \"d\"\n  \"b\"\n");
+
+    items.replace(vec!["b".to_string()]);
+    assert_eq!(root.snapshot(), "<list>\n  \"b\"\n");
+    assert_eq!(root.find_by_text("b"), Some(b));
+}
+
+#[test]
+fn for_keeps_state_map_entries_by_key() {
+    let todos = create_state_map([(1, "write".to_string()), (2, "test".to_string())]);
+    let root = render(layout! {
+        Node("list") {
+            for todo in todos.clone() where key = |todo| todo.0 {
+                Text(computed!([todo] || todo.get().1))
+            }
+        }
+    });
+    let test = root.find_by_text("test").unwrap();
+
+    todos.insert(1, "review".to_string());
+    todos.insert(3, "ship".to_string());
+    todos.move_index(1, 0);
+
+    assert_eq!(
+        root.snapshot(),
+        "<list>\n  \"test\"\n  \"review\"\n  \"ship\"\n"
+    );
+    assert_eq!(root.find_by_text("test"), Some(test));
+}
+
+/// Renders `item` with `suffix` through a computed value that counts its runs.
+fn counted_label<T: Display + Clone + 'static>(
+    item: Readonly<T>,
+    suffix: State<String>,
+    runs: Rc<Cell<usize>>,
+) -> Element {
+    let label = computed!(
+        [item, suffix] || {
+            runs.set(runs.get() + 1);
+            format!("{}{}", item.get(), suffix.get())
+        }
+    );
+    effect!(
+        [label] || {
+            label.get();
+        }
+    );
+    layout! { Text(label) }
+}
+
+#[test]
+fn for_disposes_what_an_item_created_when_the_item_is_removed() {
+    let items = create_state_vec(vec!["a".to_string(), "b".to_string()]);
+    let all = create_state(vec![1, 2]);
+    let suffix = create_state("!".to_string());
+    let runs = Rc::new(Cell::new(0));
+    let (granular_suffix, granular_runs) = (suffix.clone(), runs.clone());
+    let (reconciled_suffix, reconciled_runs) = (suffix.clone(), runs.clone());
+    let root = render(layout! {
+        Node("granular") {
+            for item in items.clone() {
+                $(counted_label(item, granular_suffix.clone(), granular_runs.clone()))
+            }
+        }
+        Node("reconciled") {
+            for item in all.clone() {
+                $(counted_label(item, reconciled_suffix.clone(), reconciled_runs.clone()))
+            }
+        }
+    });
+    assert_eq!(runs.get(), 4);
+
+    // Removed through a list diff and through reconciliation.
+    items.remove(0);
+    all.set(vec![2]);
+    runs.set(0);
+    suffix.set("?".to_string());
+
+    assert_eq!(runs.get(), 2);
+    assert_eq!(
+        root.snapshot(),
+        "<granular>\n  \"b?\"\n<reconciled>\n  \"2?\"\n"
+    );
+}
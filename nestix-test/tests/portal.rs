@@ -0,0 +1,70 @@
+use std::{any::Any, rc::Rc};
+
+use nestix::{ContextProvider, Element, Portal, Shared, component, computed, create_state, layout};
+use nestix_test::{Node, TestNode, Text, render};
+
+fn handle(node: &TestNode) -> Shared<dyn Any> {
+    Shared::from(Rc::new(node.clone()) as Rc<dyn Any>)
+}
+
+#[component]
+fn Greeting(_: &(), element: &Element) -> Element {
+    layout! {
+        Text(computed!([element] || {
+            element.context::<String>().map_or_else(String::new, |name| format!("hi {name}"))
+        }))
+    }
+}
+
+#[test]
+fn portal_children_render_into_the_target() {
+    let overlay = TestNode::tag_node("overlay");
+    let root = render(layout! {
+        Node("app") {
+            Text("before")
+            ContextProvider<String>(Rc::new("ada".to_string())) {
+                Portal(handle(&overlay)) {
+                    Node("modal") {
+                        Greeting
+                    }
+                }
+            }
+            Text("after")
+        }
+    });
+
+    assert_eq!(root.snapshot(), "<app>\n  \"before\"\n  \"after\"\n");
+    assert_eq!(overlay.snapshot(), "<modal>\n  \"hi ada\"\n");
+
+    root.unmount();
+    assert_eq!(overlay.snapshot(), "");
+}
+
+#[test]
+fn portal_moves_children_when_the_target_changes() {
+    let first = TestNode::tag_node("first");
+    let second = TestNode::tag_node("second");
+    let target = create_state(handle(&first));
+    let show = create_state(true);
+    let show_in_layout = show.clone();
+    let root = render(layout! {
+        Node("app") {
+            if show_in_layout.get() {
+                Portal(target.clone()) {
+                    Text("a")
+                    Text("b")
+                }
+            }
+        }
+    });
+
+    assert_eq!(first.snapshot(), "\"a\"\n\"b\"\n");
+
+    target.set(handle(&second));
+    assert_eq!(first.snapshot(), "");
+    assert_eq!(second.snapshot(), "\"a\"\n\"b\"\n");
+
+    show.set(false);
+    assert_eq!(second.snapshot(), "");
+    assert_eq!(root.snapshot(), "<app>\n");
+}
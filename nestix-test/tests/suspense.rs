@@ -0,0 +1,202 @@
+use std::{
+    cell::RefCell,
+    pin::Pin,
+    rc::Rc,
+    task::{Context, Poll, Waker},
+};
+
+use nestix::{
+    ContextProvider, Element, LocalExecutor, Resource, Suspense, component, computed,
+    create_resource, create_state, layout, props, set_executor,
+};
+use nestix_test::{Node, Text, render};
+
+/// A future that completes when a value is sent through its sender.
+struct Pending<T> {
+    data: Rc<RefCell<(Option<T>, Option<Waker>)>>,
+}
+
+impl<T> Future for Pending<T> {
+    type Output = T;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<T> {
+        let mut data = self.data.borrow_mut();
+        match data.0.take() {
+            Some(value) => Poll::Ready(value),
+            None => {
+                data.1 = Some(cx.waker().clone());
+                Poll::Pending
+            }
+        }
+    }
+}
+
+type Senders = Rc<RefCell<Vec<Rc<RefCell<(Option<Result<String, ()>>, Option<Waker>)>>>>>;
+
+fn send(senders: &Senders, index: usize, value: &str) {
+    let data = senders.borrow()[index].clone();
+    let mut data = data.borrow_mut();
+    data.0 = Some(Ok(value.to_string()));
+    if let Some(waker) = data.1.take() {
+        waker.wake();
+    }
+}
+
+#[props]
+struct UserNameProps {
+    name: Option<String>,
+}
+
+#[component]
+fn UserName(props: &UserNameProps) -> Element {
+    layout! {
+        Text(computed!([props.name] || name.get().unwrap_or_default()))
+    }
+}
+
+#[test]
+fn suspense_shows_fallback_until_resources_read_by_children_are_ready() {
+    let executor = LocalExecutor::new();
+    set_executor(executor.clone());
+    let senders: Senders = Rc::new(RefCell::new(Vec::new()));
+    let id = create_state(1);
+    let user: Resource<String, ()> = create_resource(id.clone(), {
+        let senders = senders.clone();
+        move |_| {
+            let data = Rc::new(RefCell::new((None, None)));
+            senders.borrow_mut().push(data.clone());
+            Pending { data }
+        }
+    });
+
+    let root = render(layout! {
+        Node("app") {
+            Text("before")
+            Suspense(.fallback = layout! { Text("loading") }) {
+                Node("user") {
+                    UserName(.name = user.clone())
+                }
+            }
+            Text("after")
+        }
+    });
+
+    assert_eq!(
+        root.snapshot(),
+        "<app>\n  \"before\"\n  \"loading\"\n  \"after\"\n"
+    );
+
+    send(&senders, 0, "ada");
+    executor.run_until_stalled();
+
+    assert_eq!(
+        root.snapshot(),
+        "<app>\n  \"before\"\n  <user>\n    \"ada\"\n  \"after\"\n"
+    );
+
+    id.set(2);
+
+    assert_eq!(
+        root.snapshot(),
+        "<app>\n  \"before\"\n  \"loading\"\n  \"after\"\n"
+    );
+
+    send(&senders, 1, "grace");
+    executor.run_until_stalled();
+
+    assert_eq!(
+        root.snapshot(),
+        "<app>\n  \"before\"\n  <user>\n    \"grace\"\n  \"after\"\n"
+    );
+
+    root.unmount();
+
+    assert!(root.container().children().is_empty());
+}
+
+#[component]
+fn Greeting(_: &(), element: &Element) -> Element {
+    layout! {
+        Text(computed!([element] || {
+            element.context::<String>().map_or_else(String::new, |greeting| greeting.to_string())
+        }))
+    }
+}
+
+#[test]
+fn hidden_suspense_content_still_resolves_contexts() {
+    let executor = LocalExecutor::new();
+    set_executor(executor.clone());
+    let senders: Senders = Rc::new(RefCell::new(Vec::new()));
+    let user: Resource<String, ()> = create_resource(create_state(1), {
+        let senders = senders.clone();
+        move |_| {
+            let data = Rc::new(RefCell::new((None, None)));
+            senders.borrow_mut().push(data.clone());
+            Pending { data }
+        }
+    });
+    let greeting = create_state(Rc::new("hello".to_string()));
+    let show_late = create_state(false);
+    let show_late_in_layout = show_late.clone();
+
+    let root = render(layout! {
+        ContextProvider<String>(greeting.clone()) {
+            Suspense(.fallback = layout! { Text("loading") }) {
+                UserName(.name = user.clone())
+                Greeting
+                if show_late_in_layout.get() {
+                    Greeting
+                }
+            }
+        }
+    });
+
+    assert_eq!(root.snapshot(), "\"loading\"\n");
+
+    // Both the rerun of a mounted consumer and a consumer mounted while the
+    // content is hidden see the provider and the renderer.
+    greeting.set(Rc::new("hi".to_string()));
+    show_late.set(true);
+
+    send(&senders, 0, "ada");
+    executor.run_until_stalled();
+
+    assert_eq!(root.snapshot(), "\"ada\"\n\"hi\"\n\"hi\"\n");
+}
+
+#[test]
+fn suspense_stops_waiting_for_resources_of_unmounted_children() {
+    let executor = LocalExecutor::new();
+    set_executor(executor.clone());
+    let senders: Senders = Rc::new(RefCell::new(Vec::new()));
+    let id = create_state(1);
+    let user: Resource<String, ()> = create_resource(id.clone(), {
+        let senders = senders.clone();
+        move |_| {
+            let data = Rc::new(RefCell::new((None, None)));
+            senders.borrow_mut().push(data.clone());
+            Pending { data }
+        }
+    });
+    let show_user = create_state(true);
+    let show_user_in_layout = show_user.clone();
+
+    let root = render(layout! {
+        Suspense(.fallback = layout! { Text("loading") }) {
+            Text("profile")
+            if show_user_in_layout.get() {
+                UserName(.name = user.clone())
+            }
+        }
+    });
+
+    send(&senders, 0, "ada");
+    executor.run_until_stalled();
+    assert_eq!(root.snapshot(), "\"profile\"\n\"ada\"\n");
+
+    show_user.set(false);
+    id.set(2);
+
+    assert_eq!(root.snapshot(), "\"profile\"\n");
+}
@@ -0,0 +1,28 @@
+use std::time::Duration;
+
+use nestix::{Element, FakeClock, component, computed, interval, layout, set_clock};
+use nestix_test::{Text, render};
+
+#[component]
+fn Ticker() -> Element {
+    let ticks = interval(Duration::from_millis(10));
+    layout! {
+        Text(computed!([ticks] || ticks.get().to_string()))
+    }
+}
+
+#[test]
+fn timers_stop_when_their_element_unmounts() {
+    let clock = FakeClock::new();
+    set_clock(clock.clone());
+    let root = render(layout! {
+        Ticker
+    });
+
+    clock.advance(Duration::from_millis(25));
+    assert_eq!(root.snapshot(), "\"2\"\n");
+    assert_eq!(clock.pending_timers(), 1);
+
+    root.unmount();
+    assert_eq!(clock.pending_timers(), 0);
+}
@@ -2,25 +2,34 @@ use std::{marker::PhantomData, rc::Rc};
 
 use nestix_macros::{component, layout, props};
 
-use crate::{Element, Layout, components::Fragment, effect};
+use crate::{ContextKey, Element, Layout, components::Fragment, effect};
 
 /// Props for [`ContextProvider`].
 #[props(bounds(T: 'static))]
 pub struct ContextProviderProps<T> {
     #[props(start)]
     value: Rc<T>,
+    /// Provides the value for this key instead of for the type `T`.
+    #[props(default)]
+    key: Option<ContextKey<T>>,
     children: Layout,
 }
 
 /// Provides a typed context value to descendant elements.
 ///
-/// Descendants can retrieve the value with [`Element::context`].
+/// Descendants can retrieve the value with [`Element::context`], or with
+/// [`Element::keyed_context`] and [`use_context`](crate::use_context) when
+/// `key` is set. When `value` changes, effects and computed values that read
+/// it rerun.
 #[component(generics(T))]
 pub fn ContextProvider<T: 'static>(props: &ContextProviderProps<T>, element: &Element) -> Element {
+    let key = props.key.get();
     effect!(
-        [element, props.value] || {
-            element.provide_context::<T>(value.get());
-        }
+        [element, props.value]
+            || match &key {
+                Some(key) => element.provide_keyed_context(key, value.get()),
+                None => element.provide_context::<T>(value.get()),
+            }
     );
 
     layout! {
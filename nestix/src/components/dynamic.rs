@@ -0,0 +1,83 @@
+use std::{fmt::Debug, rc::Rc};
+
+use nestix_macros::{component, computed, layout, props};
+
+use crate::{Component, Element, components::Fragment, create_element, memo, untrack};
+
+/// Creates the element rendered by [`Dynamic`].
+///
+/// Clones compare equal to each other, and to nothing else, so a `Dynamic`
+/// only remounts when it receives a component created separately.
+#[derive(Clone)]
+pub struct DynamicComponent {
+    create: Rc<dyn Fn() -> Element>,
+}
+
+impl DynamicComponent {
+    /// Creates a component that renders the element returned by `create`.
+    ///
+    /// Use [`create_element_with_id`](crate::create_element_with_id) in
+    /// `create` to render a component from its [`ComponentID`](crate::ComponentID)
+    /// and boxed props.
+    pub fn new(create: impl Fn() -> Element + 'static) -> Self {
+        Self {
+            create: Rc::new(create),
+        }
+    }
+
+    /// Creates a component that renders `C` with a clone of `props`.
+    pub fn of<C: Component>(props: C::Props) -> Self
+    where
+        C::Props: Clone,
+    {
+        Self::new(move || create_element::<C>(props.clone()))
+    }
+
+    fn create(&self) -> Element {
+        (self.create)()
+    }
+}
+
+impl PartialEq for DynamicComponent {
+    fn eq(&self, other: &Self) -> bool {
+        Rc::ptr_eq(&self.create, &other.create)
+    }
+}
+
+impl Debug for DynamicComponent {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("DynamicComponent").finish_non_exhaustive()
+    }
+}
+
+/// Props for [`Dynamic`].
+#[props]
+pub struct DynamicProps {
+    /// The component to render.
+    #[props(start)]
+    component: DynamicComponent,
+}
+
+/// Renders a component chosen at runtime.
+///
+/// When `component` changes to a different [`DynamicComponent`], the current
+/// subtree is unmounted and the new component is mounted in its place.
+#[component]
+pub fn Dynamic(props: &DynamicProps) -> Element {
+    let component = memo({
+        let component = props.component.clone();
+        move || component.get()
+    });
+    // Creating the element may read unrelated signals; they must not cause
+    // the subtree to be recreated.
+    let children = computed!(
+        [component] || {
+            let component = component.get();
+            untrack(|| component.create())
+        }
+    );
+
+    layout! {
+        Fragment(.children = children)
+    }
+}
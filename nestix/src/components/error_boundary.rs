@@ -0,0 +1,165 @@
+use std::{
+    cell::{Cell, RefCell},
+    panic::{AssertUnwindSafe, catch_unwind},
+    rc::Rc,
+};
+
+use nestix_macros::{component, props};
+use nestix_signal::{CaughtPanic, ErrorHandler, current_error_handler, with_error_handler};
+
+use crate::{
+    ComponentOutput, Element, Fragment, FragmentProps, Layout, PropValue, Shared, WeakElement,
+    create_element, effect, untrack,
+};
+
+/// Builds the fallback element from a caught panic and a reset callback.
+pub type ErrorBoundaryFallback = Shared<dyn Fn(CaughtPanic, Shared<dyn Fn()>) -> Element>;
+
+/// Props for [`ErrorBoundary`].
+#[props]
+pub struct ErrorBoundaryProps {
+    fallback: ErrorBoundaryFallback,
+    #[props(default)]
+    children: Layout,
+}
+
+/// Contains panics raised by its subtree.
+///
+/// Panics from mounting the children, and from effects created by them, are
+/// caught instead of unwinding through the runtime. The boundary then unmounts
+/// its children and mounts the element returned by `fallback`. Calling the
+/// reset callback passed to `fallback`, or changing any prop, mounts the
+/// children again.
+///
+/// Panics raised by the fallback itself propagate to the nearest enclosing
+/// boundary.
+#[component]
+pub fn ErrorBoundary(props: &ErrorBoundaryProps, element: &Element) {
+    let boundary = Rc::new(Boundary {
+        element: element.downgrade(),
+        children: RefCell::new(Layout::from(())),
+        fallback: RefCell::new(None),
+        error: RefCell::new(None),
+        pending_error: RefCell::new(None),
+        mounting: Cell::new(false),
+        unmounted: Cell::new(false),
+        outer_handler: current_error_handler(),
+    });
+
+    element.on_unmount({
+        let boundary = boundary.clone();
+        move || boundary.unmounted.set(true)
+    });
+
+    effect!(
+        [boundary, props.children, props.fallback] || {
+            boundary.children.replace(children.get());
+            boundary.fallback.replace(Some(fallback.get()));
+            boundary.error.take();
+            // Mounting may read unrelated signals; they must not become
+            // dependencies of this effect.
+            untrack(|| boundary.render());
+        }
+    );
+}
+
+struct Boundary {
+    element: WeakElement,
+    children: RefCell<Layout>,
+    fallback: RefCell<Option<ErrorBoundaryFallback>>,
+    error: RefCell<Option<CaughtPanic>>,
+    pending_error: RefCell<Option<CaughtPanic>>,
+    mounting: Cell<bool>,
+    unmounted: Cell<bool>,
+    outer_handler: Option<ErrorHandler>,
+}
+
+impl Boundary {
+    fn render(self: &Rc<Self>) {
+        if self.unmounted.get() {
+            return;
+        }
+        let Some(element) = self.element.upgrade() else {
+            return;
+        };
+
+        for child in element.take_children() {
+            child.unmount();
+        }
+
+        // Rendering also happens outside the boundary's effect, so what the
+        // fallback creates belongs to the boundary's scope.
+        let error = self.error.borrow().clone();
+        element.scope().run(|| match error {
+            None => self.mount_children(&element),
+            Some(error) => self.mount_fallback(&element, error),
+        });
+    }
+
+    fn mount_children(self: &Rc<Self>, element: &Element) {
+        let child = create_element::<Fragment>(FragmentProps {
+            children: PropValue::from_plain(self.children.borrow().clone()),
+        });
+
+        let handler = {
+            let boundary = Rc::downgrade(self);
+            Shared::from(Rc::new(move |error: CaughtPanic| {
+                if let Some(boundary) = boundary.upgrade() {
+                    boundary.fail(error);
+                }
+            }) as Rc<dyn Fn(CaughtPanic)>)
+        };
+
+        self.mounting.set(true);
+        let result = with_error_handler(Some(handler), || {
+            catch_unwind(AssertUnwindSafe(|| child.mount(Some(element))))
+        });
+        self.mounting.set(false);
+
+        let error = self
+            .pending_error
+            .take()
+            .or_else(|| result.err().map(CaughtPanic::from_payload));
+        if let Some(error) = error {
+            self.fail(error);
+        }
+    }
+
+    fn mount_fallback(self: &Rc<Self>, element: &Element, error: CaughtPanic) {
+        let Some(fallback) = self.fallback.borrow().clone() else {
+            return;
+        };
+
+        let reset = {
+            let boundary = Rc::downgrade(self);
+            Shared::from(Rc::new(move || {
+                if let Some(boundary) = boundary.upgrade() {
+                    boundary.reset();
+                }
+            }) as Rc<dyn Fn()>)
+        };
+
+        with_error_handler(self.outer_handler.clone(), || {
+            fallback(error, reset).mount(Some(element));
+        });
+    }
+
+    fn fail(self: &Rc<Self>, error: CaughtPanic) {
+        if self.mounting.get() {
+            let mut pending_error = self.pending_error.borrow_mut();
+            if pending_error.is_none() {
+                *pending_error = Some(error);
+            }
+            return;
+        }
+
+        self.error.replace(Some(error));
+        untrack(|| self.render());
+    }
+
+    fn reset(self: &Rc<Self>) {
+        if self.error.take().is_some() {
+            untrack(|| self.render());
+        }
+    }
+}
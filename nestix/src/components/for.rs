@@ -1,7 +1,7 @@
 use std::{cell::RefCell, hash::Hash, marker::PhantomData, rc::Rc};
 
 use nestix_macros::{component, props};
-use nestix_signal::{Readonly, Signal, State, create_state};
+use nestix_signal::{ListDiff, ListDiffs, Readonly, Scope, Signal, State, create_state};
 
 use crate::{
     ComponentOutput, Element, PropValue, Shared, effect, untrack,
@@ -11,12 +11,16 @@ use crate::{
 /// Props for [`For`].
 ///
 /// `data` supplies the items, `key` provides stable identity for each item, and
-/// `children` creates an element for an item signal.
+/// `children` creates an element for an item signal. `diffs`, when present,
+/// receives the individual changes made to `data` so they can be applied
+/// without reconciling the whole list.
 #[props(bounds(I: IntoIterator + 'static, K: 'static))]
 pub struct ForProps<I: IntoIterator, K> {
     data: I,
     key: Shared<dyn Fn(&<I as IntoIterator>::Item) -> K>,
     children: Shared<dyn Fn(Readonly<<I as IntoIterator>::Item>) -> PropValue<Element>>,
+    #[props(default)]
+    diffs: Option<ListDiffs<<I as IntoIterator>::Item>>,
 }
 
 #[doc(hidden)]
@@ -30,6 +34,7 @@ where
     Key: Fn(&<S::Output as IntoIterator>::Item) -> K + 'static,
     Children: Fn(Readonly<<S::Output as IntoIterator>::Item>) -> PropValue<Element> + 'static,
 {
+    let diffs = data.list_diffs();
     crate::create_element::<For<S::Output, K>>(ForProps {
         data: PropValue::from_signal(data),
         key: PropValue::from_plain(Shared::from(
@@ -37,6 +42,7 @@ where
         )),
         children: PropValue::from_plain(Shared::from(Rc::new(children)
             as Rc<dyn Fn(Readonly<<S::Output as IntoIterator>::Item>) -> PropValue<Element>>)),
+        diffs: PropValue::from_plain(diffs),
     })
 }
 
@@ -56,10 +62,120 @@ where
     )
 }
 
+type KeyFn<T, K> = Shared<dyn Fn(&T) -> K>;
+type ChildrenFn<T> = Shared<dyn Fn(Readonly<T>) -> PropValue<Element>>;
+
+/// The rendered items of a [`For`] element, in the order of its children.
+struct ForItems<T, K> {
+    signals: Vec<State<T>>,
+    /// Owns what the children callback created for each item.
+    scopes: Vec<Scope>,
+    keys: Vec<K>,
+    key_fn: Option<KeyFn<T, K>>,
+    children_fn: Option<ChildrenFn<T>>,
+}
+
+impl<T: Eq + Clone + 'static, K: Eq> ForItems<T, K> {
+    /// Applies a single list change to the children of `element`.
+    ///
+    /// Children after the changed position are placed again because their
+    /// previous siblings changed.
+    fn apply(&mut self, element: &Element, diff: ListDiff<T>) {
+        match diff {
+            ListDiff::Insert { index, value } => self.insert(element, index, value),
+            ListDiff::Remove { index } => {
+                self.remove(element, index);
+                notify_place_from(element, index);
+            }
+            ListDiff::Move { from, to } => {
+                let mut children = element.take_children();
+                let child = children.remove(from);
+                children.insert(to, child);
+                for child in children {
+                    element.add_child(child);
+                }
+                let signal = self.signals.remove(from);
+                self.signals.insert(to, signal);
+                let scope = self.scopes.remove(from);
+                self.scopes.insert(to, scope);
+                let key = self.keys.remove(from);
+                self.keys.insert(to, key);
+                notify_place_from(element, from.min(to));
+            }
+            ListDiff::Update { index, value } => {
+                let key_fn = self.key_fn.clone().unwrap();
+                if key_fn(&value) == self.keys[index] {
+                    self.signals[index].set(value);
+                } else {
+                    self.remove(element, index);
+                    self.insert(element, index, value);
+                }
+            }
+            ListDiff::Replace => unreachable!("replaced lists are reconciled in full"),
+        }
+    }
+
+    fn insert(&mut self, element: &Element, index: usize, value: T) {
+        let key_fn = self.key_fn.clone().unwrap();
+        let children_fn = self.children_fn.clone().unwrap();
+        let mut children = element.take_children();
+        let later_children = children.split_off(index);
+        for child in children {
+            element.add_child(child);
+        }
+
+        let signal = create_state(value.clone());
+        let (scope, child) = render_item(element, &children_fn, &signal);
+        child.set_in_list(true);
+        child.mount(Some(element));
+
+        for child in later_children {
+            element.add_child(child.clone());
+            child.notify_place(true);
+        }
+        self.keys.insert(index, key_fn(&value));
+        self.signals.insert(index, signal);
+        self.scopes.insert(index, scope);
+    }
+
+    fn remove(&mut self, element: &Element, index: usize) {
+        let child = element.children()[index].clone();
+        child.unmount();
+        self.keys.remove(index);
+        self.signals.remove(index);
+        self.scopes.remove(index).dispose();
+    }
+}
+
+/// Creates the child for an item, along with the scope that owns the effects
+/// and computed values created for it.
+///
+/// The child outlives the effect run that creates it, so its scope is nested
+/// in the scope of `element` and disposed when the item is removed.
+fn render_item<T: Clone + 'static>(
+    element: &Element,
+    children_fn: &ChildrenFn<T>,
+    signal: &State<T>,
+) -> (Scope, Element) {
+    let scope = element.scope().child();
+    let child = scope.run(|| children_fn(signal.clone().into_readonly()).get());
+    (scope, child)
+}
+
+fn notify_place_from(element: &Element, index: usize) {
+    for child in element.children().into_iter().skip(index) {
+        child.notify_place(true);
+    }
+}
+
 /// Renders a keyed list of elements.
 ///
 /// Existing children are reused by key. Each rendered child receives a readonly
 /// signal for its item, so reused children can react to item value changes.
+///
+/// When `data` is a list signal such as [`StateVec`](nestix_signal::StateVec)
+/// or [`StateMap`](nestix_signal::StateMap), its individual changes are
+/// applied directly instead of reconciling the whole list.
 #[component(generics(I, K))]
 pub fn For<I: IntoIterator + Clone + 'static, K: Eq + Hash + 'static>(
     props: &ForProps<I, K>,
@@ -67,45 +183,78 @@ pub fn For<I: IntoIterator + Clone + 'static, K: Eq + Hash + 'static>(
 ) where
     I::Item: Eq + Clone,
 {
-    let prev_signals: Rc<RefCell<Vec<State<<I as IntoIterator>::Item>>>> =
-        Rc::new(RefCell::new(vec![]));
-    let prev_keys: Rc<RefCell<Vec<K>>> = Rc::new(RefCell::new(vec![]));
+    let items: Rc<RefCell<ForItems<<I as IntoIterator>::Item, K>>> =
+        Rc::new(RefCell::new(ForItems {
+            signals: vec![],
+            scopes: vec![],
+            keys: vec![],
+            key_fn: None,
+            children_fn: None,
+        }));
 
     effect!(
-        [element, props.data, props.key, props.children] || {
+        [
+            element,
+            items,
+            props.data,
+            props.key,
+            props.children,
+            props.diffs
+        ] || {
             let key_fn = key.get();
             let children_fn = children.get();
+            let pending = diffs.get().map(|diffs| diffs.take()).unwrap_or_default();
+
+            // Changes reported by the list can be applied one by one as long
+            // as the children were rendered with the same callbacks.
+            let granular = {
+                let items = items.borrow();
+                !pending.is_empty()
+                    && !pending.iter().any(|diff| matches!(diff, ListDiff::Replace))
+                    && items.key_fn.as_ref() == Some(&key_fn)
+                    && items.children_fn.as_ref() == Some(&children_fn)
+            };
+            if granular {
+                untrack(|| {
+                    let mut items = items.borrow_mut();
+                    for diff in pending {
+                        items.apply(&element, diff);
+                    }
+                    element.notify_last_handle_change();
+                });
+                return;
+            }
+
             let next_data = data.get().into_iter().collect::<Vec<_>>();
-            let next_keys = next_data
-                .iter()
-                .map(|item| key_fn(item))
-                .collect::<Vec<_>>();
+            let next_keys = next_data.iter().map(&*key_fn).collect::<Vec<_>>();
             // Lifecycle and placement callbacks may access unrelated signals;
             // they must not become dependencies of this reconciliation effect.
             untrack(|| {
                 let prev_children = element.take_children();
-                let mut prev_signals = prev_signals.borrow_mut();
-                let mut prev_keys = prev_keys.borrow_mut();
-                let result = reconcile(&*prev_keys, &next_keys);
+                let mut items = items.borrow_mut();
+                let result = reconcile(&items.keys, &next_keys);
                 let ReconcileResult { removed, mapping } = result;
 
                 for prev_i in removed {
                     prev_children[prev_i].unmount();
+                    items.scopes[prev_i].dispose();
                 }
 
                 let mut next_children: Vec<Element> = Vec::new();
                 let mut next_signals: Vec<State<<I as IntoIterator>::Item>> = Vec::new();
+                let mut next_scopes: Vec<Scope> = Vec::new();
                 let mut previous_siblings_changed = false;
                 for (i, prev_i) in mapping.iter().enumerate() {
-                    let (signal, child) = if let Some(prev_i) = prev_i {
-                        let signal = prev_signals[*prev_i].clone();
+                    let (signal, scope, child) = if let Some(prev_i) = prev_i {
+                        let signal = items.signals[*prev_i].clone();
+                        let scope = items.scopes[*prev_i].clone();
                         let child = prev_children[*prev_i].clone();
                         signal.set(next_data[i].clone());
-                        (signal, child)
+                        (signal, scope, child)
                     } else {
                         let signal = create_state(next_data[i].clone());
-                        let child = children_fn(signal.clone().into_readonly()).get();
-                        (signal, child)
+                        let (scope, child) = render_item(&element, &children_fn, &signal);
+                        (signal, scope, child)
                     };
 
                     if let Some(prev_i) = *prev_i {
@@ -135,11 +284,15 @@ pub fn For<I: IntoIterator + Clone + 'static, K: Eq + Hash + 'static>(
                     }
 
                     next_signals.push(signal);
+                    next_scopes.push(scope);
                     next_children.push(child);
                 }
 
-                *prev_keys = next_keys;
-                *prev_signals = next_signals;
+                items.keys = next_keys;
+                items.signals = next_signals;
+                items.scopes = next_scopes;
+                items.key_fn = Some(key_fn);
+                items.children_fn = Some(children_fn);
                 element.notify_last_handle_change();
             });
         }
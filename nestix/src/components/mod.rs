@@ -1,13 +1,31 @@
 /// Context provider component.
 pub mod context_provider;
+/// Runtime-selected component.
+pub mod dynamic;
+/// Error boundary component.
+pub mod error_boundary;
 /// List rendering component.
 pub mod r#for;
 /// Fragment component.
 pub mod fragment;
+/// Portal component.
+pub mod portal;
+/// Conditional rendering component.
+pub mod show;
+/// Suspense component.
+pub mod suspense;
+/// Multi-branch conditional rendering components.
+pub mod switch;
 
 pub use context_provider::*;
+pub use dynamic::*;
+pub use error_boundary::*;
 pub use r#for::*;
 pub use fragment::*;
+pub use portal::*;
+pub use show::*;
+pub use suspense::*;
+pub use switch::*;
 
 use std::{any::TypeId, hash::Hash};
 
@@ -30,9 +48,9 @@ pub trait Component: 'static {
 /// Component IDs compare and hash by Rust [`TypeId`].
 #[derive(Debug, Clone, Copy)]
 pub struct ComponentID {
-    #[allow(unused)]
     pub(crate) name: &'static str,
     pub(crate) type_id: TypeId,
+    pub(crate) props_type_id: TypeId,
     pub(crate) mount_fn: fn(&Element),
 }
 
@@ -55,6 +73,7 @@ pub fn component_id<C: Component>() -> ComponentID {
     ComponentID {
         name: std::any::type_name::<C>(),
         type_id: TypeId::of::<C>(),
+        props_type_id: TypeId::of::<C::Props>(),
         mount_fn: C::on_mount,
     }
 }
@@ -0,0 +1,32 @@
+use std::any::Any;
+
+use nestix_macros::{component, layout, props};
+
+use crate::{Element, Layout, Shared, components::Fragment, effect};
+
+/// Props for [`Portal`].
+#[props]
+pub struct PortalProps {
+    /// Host handle that the children are placed under.
+    #[props(start)]
+    target: Shared<dyn Any>,
+    children: Layout,
+}
+
+/// Places its children under a different host parent.
+///
+/// Host nodes rendered by the children report `target` as their
+/// [`Placement::parent`](crate::Placement::parent) instead of the handle of
+/// their nearest host ancestor, and are placed before any nodes the target
+/// already has. The children stay in the logical tree, so they still see the
+/// contexts provided above the portal and are unmounted with it.
+///
+/// `target` must be a handle of the node type the renderer creates.
+#[component]
+pub fn Portal(props: &PortalProps, element: &Element) -> Element {
+    effect!([element, props.target] || element.set_portal_target(target.get()));
+
+    layout! {
+        Fragment(.children = props.children.clone())
+    }
+}
@@ -0,0 +1,74 @@
+use std::{cell::RefCell, rc::Rc};
+
+use nestix_macros::{component, props};
+
+use crate::{
+    ComponentOutput, Element, Fragment, FragmentProps, Layout, PropValue, create_element, effect,
+    untrack,
+};
+
+/// Props for [`Show`].
+#[props]
+pub struct ShowProps {
+    when: bool,
+    #[props(default)]
+    fallback: Layout,
+    #[props(default)]
+    children: Layout,
+}
+
+/// Renders its children while `when` is `true`, and `fallback` otherwise.
+///
+/// The mounted branch is only replaced when `when` changes or the layout of
+/// the shown branch is replaced, so the state of its elements survives any
+/// other update.
+#[component]
+pub fn Show(props: &ShowProps, element: &Element) {
+    let branch = Rc::new(MountedBranch::new());
+
+    effect!(
+        [element, branch, props.when, props.children, props.fallback] || {
+            let when = when.get();
+            let layout = if when { children.get() } else { fallback.get() };
+            // Mounting a branch may read unrelated signals; they must not
+            // become dependencies of this effect.
+            untrack(|| {
+                branch.switch_to(&element, (when, layout.clone()), || {
+                    create_element::<Fragment>(FragmentProps {
+                        children: PropValue::from_plain(layout),
+                    })
+                });
+            });
+        }
+    );
+}
+
+/// The branch of a control-flow component that is currently mounted.
+pub(crate) struct MountedBranch<K> {
+    current: RefCell<Option<(K, Element)>>,
+}
+
+impl<K: PartialEq> MountedBranch<K> {
+    pub(crate) fn new() -> Self {
+        Self {
+            current: RefCell::new(None),
+        }
+    }
+
+    /// Mounts the element created by `create` as the only child of `parent`,
+    /// unless the branch identified by `key` is already mounted.
+    pub(crate) fn switch_to(&self, parent: &Element, key: K, create: impl FnOnce() -> Element) {
+        if let Some((current_key, _)) = &*self.current.borrow()
+            && *current_key == key
+        {
+            return;
+        }
+
+        if let Some((_, previous)) = self.current.take() {
+            previous.unmount();
+        }
+        let next = create();
+        next.mount(Some(parent));
+        self.current.replace(Some((key, next)));
+    }
+}
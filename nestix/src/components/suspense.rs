@@ -0,0 +1,82 @@
+use std::{
+    cell::{Cell, RefCell},
+    rc::Rc,
+};
+
+use nestix_macros::{component, props};
+use nestix_signal::{SuspenseTracker, with_suspense};
+
+use crate::{
+    ComponentOutput, Element, Fragment, FragmentProps, Layout, PropValue, create_element,
+    scoped_effect, untrack,
+};
+
+/// Props for [`Suspense`].
+#[props]
+pub struct SuspenseProps {
+    #[props(default)]
+    fallback: Layout,
+    #[props(default)]
+    children: Layout,
+}
+
+/// Renders `fallback` while a resource read inside its children is loading.
+///
+/// The children stay mounted while the fallback is shown, so resources they
+/// own keep loading; their host nodes are removed from the host tree until
+/// every resource they read is no longer loading.
+#[component]
+pub fn Suspense(props: &SuspenseProps, element: &Element) {
+    let tracker = SuspenseTracker::new();
+    let content = create_element::<Fragment>(FragmentProps {
+        children: props.children.clone(),
+    });
+    let fallback_element: Rc<RefCell<Option<Element>>> = Rc::new(RefCell::new(None));
+    let hidden = Rc::new(Cell::new(false));
+
+    with_suspense(Some(tracker.clone()), || content.mount(Some(element)));
+
+    element.on_unmount({
+        let content = content.clone();
+        let hidden = hidden.clone();
+        move || {
+            if hidden.get() {
+                content.unmount();
+            }
+        }
+    });
+
+    scoped_effect!(
+        [
+            element,
+            tracker,
+            content,
+            fallback_element,
+            hidden,
+            props.fallback
+        ] || {
+            let pending = tracker.is_pending();
+            let fallback = fallback.get();
+            // Mounting the fallback may read unrelated signals; they must not
+            // become dependencies of this effect.
+            untrack(|| {
+                if let Some(fallback_element) = fallback_element.take() {
+                    fallback_element.unmount();
+                }
+
+                if pending {
+                    if !hidden.replace(true) {
+                        content.detach();
+                    }
+                    let next = create_element::<Fragment>(FragmentProps {
+                        children: PropValue::from_plain(fallback),
+                    });
+                    next.mount(Some(&element));
+                    fallback_element.replace(Some(next));
+                } else if hidden.replace(false) {
+                    content.attach(&element);
+                }
+            });
+        }
+    );
+}
@@ -0,0 +1,74 @@
+use std::rc::Rc;
+
+use nestix_macros::{component, layout, props};
+
+use crate::{
+    Element, Fragment, FragmentProps, Layout, PropValue, components::show::MountedBranch,
+    create_element, effect, untrack,
+};
+
+/// Props for [`Switch`].
+#[props]
+pub struct SwitchProps {
+    #[props(default)]
+    fallback: Layout,
+    #[props(default)]
+    children: Layout,
+}
+
+/// Renders the first [`Match`] child whose `when` is `true`, or `fallback`
+/// when none is.
+///
+/// Conditions are read in order, and only up to the first match. The mounted
+/// branch is only replaced when a different `Match` element or fallback
+/// layout is chosen, so the state of its elements survives any other update.
+/// Children that are not `Match` elements are ignored.
+#[component]
+pub fn Switch(props: &SwitchProps, element: &Element) {
+    let branch = Rc::new(MountedBranch::new());
+
+    effect!(
+        [element, branch, props.children, props.fallback] || {
+            let children = children.get();
+            let chosen = children.iter().position(|child| {
+                child
+                    .props()
+                    .downcast_ref::<MatchProps>()
+                    .is_some_and(|props| props.when.get())
+            });
+            let layout = match chosen {
+                Some(index) => Layout::from(children[index].clone()),
+                None => fallback.get(),
+            };
+            // Mounting a branch may read unrelated signals; they must not
+            // become dependencies of this effect.
+            untrack(|| {
+                branch.switch_to(&element, (chosen, layout.clone()), || match chosen {
+                    Some(index) => children[index].clone(),
+                    None => create_element::<Fragment>(FragmentProps {
+                        children: PropValue::from_plain(layout),
+                    }),
+                });
+            });
+        }
+    );
+}
+
+/// Props for [`Match`].
+#[props]
+pub struct MatchProps {
+    when: bool,
+    #[props(default)]
+    children: Layout,
+}
+
+/// A branch of a [`Switch`], rendered while it is the first child whose
+/// `when` is `true`.
+///
+/// Outside a `Switch`, a `Match` always renders its children.
+#[component]
+pub fn Match(props: &MatchProps) -> Element {
+    layout! {
+        Fragment(.children = props.children.clone())
+    }
+}
@@ -0,0 +1,139 @@
+use std::{
+    any::TypeId,
+    fmt::{Debug, Display},
+    hash::Hash,
+    marker::PhantomData,
+    rc::Rc,
+    sync::atomic::{AtomicU64, Ordering},
+};
+
+use crate::current_element;
+
+static NEXT_CONTEXT_KEY: AtomicU64 = AtomicU64::new(0);
+
+/// Identifies a context slot on an element.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub(crate) enum ContextId {
+    Type(TypeId),
+    Key(u64),
+}
+
+/// A typed key that identifies a context value.
+///
+/// Every key created with [`ContextKey::new`] is distinct, even when two keys
+/// share a name or a value type, so several providers of the same type can
+/// coexist. Copies of a key refer to the same context.
+///
+/// ```
+/// use std::sync::LazyLock;
+///
+/// use nestix::ContextKey;
+///
+/// static ACCENT_COLOR: LazyLock<ContextKey<String>> =
+///     LazyLock::new(|| ContextKey::new("accent color"));
+/// static TEXT_COLOR: LazyLock<ContextKey<String>> =
+///     LazyLock::new(|| ContextKey::new("text color"));
+///
+/// assert_ne!(*ACCENT_COLOR, *TEXT_COLOR);
+/// ```
+pub struct ContextKey<T> {
+    id: u64,
+    name: &'static str,
+    _marker: PhantomData<fn() -> T>,
+}
+
+impl<T> ContextKey<T> {
+    /// Creates a new key. `name` is used in error messages.
+    pub fn new(name: &'static str) -> Self {
+        Self {
+            id: NEXT_CONTEXT_KEY.fetch_add(1, Ordering::Relaxed),
+            name,
+            _marker: PhantomData,
+        }
+    }
+
+    /// Returns the name this key was created with.
+    pub fn name(&self) -> &'static str {
+        self.name
+    }
+
+    pub(crate) fn context_id(&self) -> ContextId {
+        ContextId::Key(self.id)
+    }
+}
+
+impl<T> Clone for ContextKey<T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Copy for ContextKey<T> {}
+
+impl<T> Debug for ContextKey<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ContextKey")
+            .field("name", &self.name)
+            .field("id", &self.id)
+            .finish()
+    }
+}
+
+impl<T> PartialEq for ContextKey<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.id == other.id
+    }
+}
+
+impl<T> Eq for ContextKey<T> {}
+
+impl<T> Hash for ContextKey<T> {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        self.id.hash(state);
+    }
+}
+
+/// An error returned by [`use_context`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ContextError {
+    /// The context was looked up outside a component function.
+    OutsideComponent(&'static str),
+    /// No ancestor of the current element provides the context.
+    Missing(&'static str),
+}
+
+impl ContextError {
+    /// Returns the name of the key that was looked up.
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::OutsideComponent(name) | Self::Missing(name) => name,
+        }
+    }
+}
+
+impl Display for ContextError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::OutsideComponent(name) => {
+                write!(f, "context `{name}` was looked up outside a component")
+            }
+            Self::Missing(name) => write!(f, "no provider for context `{name}`"),
+        }
+    }
+}
+
+impl std::error::Error for ContextError {}
+
+/// Looks up the value provided for `key` from the component that is currently
+/// mounting.
+///
+/// This is equivalent to calling [`Element::keyed_context`](crate::Element::keyed_context)
+/// on the current element, so the read is tracked in the same way. Unlike
+/// [`Element::keyed_context`](crate::Element::keyed_context), the error names
+/// the missing key.
+pub fn use_context<T: 'static>(key: &ContextKey<T>) -> Result<Rc<T>, ContextError> {
+    let element = current_element().ok_or(ContextError::OutsideComponent(key.name))?;
+    element
+        .keyed_context(key)
+        .ok_or(ContextError::Missing(key.name))
+}
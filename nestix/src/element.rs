@@ -7,11 +7,14 @@ use std::{
     rc::{Rc, Weak},
 };
 
-use crate::{Component, ComponentID, Shared, component_id, prop::Props};
-use nestix_signal::{EffectHandle, effect};
+use crate::{
+    Component, ComponentID, ContextKey, Shared, component_id, context::ContextId, prop::Props,
+};
+use nestix_signal::{EffectHandle, Scope, State, create_state, untrack};
 
 thread_local! {
-    static MOUNTED_ROOT: RefCell<Option<Element>> = const { RefCell::new(None) };
+    static MOUNTED_ROOTS: RefCell<Vec<Element>> = const { RefCell::new(Vec::new()) };
+    static LAST_ROOT: RefCell<Option<Element>> = const { RefCell::new(None) };
     static CURRENT_ELEMENT: RefCell<Option<Element>> = const { RefCell::new(None) };
 }
 
@@ -23,6 +26,11 @@ impl Drop for CurrentElementGuard {
     }
 }
 
+/// Returns the element whose component is currently mounting, if any.
+pub(crate) fn current_element() -> Option<Element> {
+    CURRENT_ELEMENT.with(|current| current.borrow().clone())
+}
+
 fn with_current_element<T>(element: &Element, f: impl FnOnce() -> T) -> T {
     let previous = CURRENT_ELEMENT.with(|current| current.replace(Some(element.clone())));
     let _guard = CurrentElementGuard(previous);
@@ -56,11 +64,23 @@ impl ComponentOutput for Element {
     #[inline]
     fn mount(&self, parent: Option<&Element>) {
         if let Some(parent) = parent {
-            self.extend_contexts(parent.contexts());
             parent.add_child(self.clone());
         }
         self.data.parent.replace(parent.map(Element::downgrade));
-        with_current_element(self, || (self.component_id().mount_fn)(self));
+        self.data
+            .context_parent
+            .replace(parent.map(Element::downgrade));
+        // The scope belongs to the parent element rather than to the effect
+        // that mounts this element, which may rerun while it stays mounted.
+        // An element that is mounted again after unmounting gets a new scope.
+        let scope = {
+            let mut scope = self.data.scope.borrow_mut();
+            if scope.as_ref().is_none_or(Scope::is_disposed) {
+                scope.replace(parent.map_or_else(Scope::new, |parent| parent.scope().child()));
+            }
+            scope.clone().unwrap()
+        };
+        with_current_element(self, || scope.run(|| (self.component_id().mount_fn)(self)));
         self.notify_after_mount();
         self.notify_place(false);
         if let Some(parent) = parent {
@@ -87,21 +107,28 @@ impl<I: IntoIterator<Item = Element>> ToElements for I {
     }
 }
 
+type LastHandleChangeCallback = Shared<dyn Fn(Option<Shared<dyn Any>>)>;
+type PlaceCallback = Shared<dyn Fn(&Placement)>;
+
 #[derive(Debug)]
 struct ElementData {
     component_id: ComponentID,
     props: Box<dyn Props>,
-    contexts: RefCell<HashMap<TypeId, Rc<dyn Any>>>,
+    contexts: RefCell<HashMap<ContextId, State<Rc<dyn Any>>>>,
     handle: RefCell<Option<Shared<dyn Any>>>,
+    portal_target: RefCell<Option<Shared<dyn Any>>>,
     parent: RefCell<Option<WeakElement>>,
+    /// The parent that contexts are resolved through. Unlike `parent`, it is
+    /// kept while the element is detached.
+    context_parent: RefCell<Option<WeakElement>>,
     children: RefCell<Vec<Element>>,
     in_list: Cell<bool>,
     last_handle_snapshot: RefCell<Option<Shared<dyn Any>>>,
-    on_last_handle_change_callbacks: RefCell<HashSet<Shared<dyn Fn(Option<Shared<dyn Any>>)>>>,
-    scoped_effect_cleanup_callbacks: RefCell<HashSet<Shared<dyn Fn()>>>,
+    on_last_handle_change_callbacks: RefCell<HashSet<LastHandleChangeCallback>>,
+    scope: RefCell<Option<Scope>>,
     on_unmount_callbacks: RefCell<HashSet<Shared<dyn Fn()>>>,
     after_mount_callbacks: RefCell<HashSet<Shared<dyn Fn()>>>,
-    on_place_callbacks: RefCell<HashSet<Shared<dyn Fn(&Placement)>>>,
+    on_place_callbacks: RefCell<HashSet<PlaceCallback>>,
 }
 
 /// A node in the Nestix component tree.
@@ -169,9 +196,9 @@ impl Element {
     }
 
     fn cancel_scoped_effects_recursively(&self) {
-        let scoped_effect_cleanup_callbacks = self.data.scoped_effect_cleanup_callbacks.take();
-        for callback in scoped_effect_cleanup_callbacks {
-            callback();
+        let scope = self.data.scope.borrow().clone();
+        if let Some(scope) = scope {
+            scope.dispose();
         }
 
         let children = self.data.children.borrow().clone();
@@ -193,41 +220,64 @@ impl Element {
 
         let parent = self.parent();
         self.data.parent.take();
-        if let Some(parent) = parent {
-            if parent.remove_child(self) {
-                parent.notify_last_handle_change();
-            }
+        self.data.context_parent.take();
+        if let Some(parent) = parent
+            && parent.remove_child(self)
+        {
+            parent.notify_last_handle_change();
         }
 
+        self.data.handle.take();
         self.data.after_mount_callbacks.take();
         self.data.on_last_handle_change_callbacks.take();
         self.data.on_place_callbacks.take();
     }
 
-    /// Returns the nearest preceding host handle in the nearest list.
+    /// Returns the nearest preceding host handle under the same host parent.
     ///
-    /// Logical siblings that do not render a host object are skipped.
+    /// Logical siblings that do not render a host object are skipped. When no
+    /// sibling in the nearest list has a handle, the search continues with the
+    /// siblings of the enclosing list, up to the nearest host ancestor.
     pub fn pred_handle(&self) -> Option<Shared<dyn Any>> {
-        self.previous_siblings()
-            .into_iter()
-            .find_map(|sibling| sibling.last_handle())
+        let parent = self.parent()?;
+
+        if self.is_in_list() {
+            let siblings = self.previous_siblings();
+            if let Some(handle) = siblings.iter().find_map(Element::last_handle) {
+                return Some(handle);
+            }
+        }
+
+        if parent.handle().is_some() || parent.is_portal() {
+            return None;
+        }
+        parent.pred_handle()
     }
 
     /// Returns the last host handle in this element's subtree.
+    ///
+    /// Host nodes placed under a portal target are not part of the result.
     pub fn last_handle(&self) -> Option<Shared<dyn Any>> {
         if let Some(handle) = self.handle() {
             return Some(handle);
         }
+        if self.is_portal() {
+            return None;
+        }
 
         let last_node = self.data.children.borrow().last().cloned()?;
         last_node.last_handle()
     }
 
     /// Returns the nearest ancestor host handle.
+    ///
+    /// Inside a [`Portal`](crate::Portal), this is the portal's target.
     pub fn parent_handle(&self) -> Option<Shared<dyn Any>> {
         let parent = self.parent()?;
         if let Some(handle) = parent.handle() {
             Some(handle)
+        } else if let Some(target) = parent.data.portal_target.borrow().clone() {
+            Some(target)
         } else {
             parent.parent_handle()
         }
@@ -312,30 +362,27 @@ impl Element {
         on_unmount_callbacks.insert(callback);
     }
 
-    fn on_scoped_effect_cleanup(&self, f: impl Fn() + 'static) {
-        let callback = Shared::from(Rc::new(f) as Rc<dyn Fn()>);
-        self.data
-            .scoped_effect_cleanup_callbacks
-            .borrow_mut()
-            .insert(callback);
+    /// Returns the scope that owns the effects and computed values created
+    /// while this element's component mounts.
+    ///
+    /// When the element mounts, the scope is created as a child of its parent
+    /// element's scope. Before that, it is created on first use as a child of
+    /// the current scope. It is disposed when this element unmounts, and
+    /// mounting the element again replaces it with a new one.
+    pub fn scope(&self) -> Scope {
+        let mut scope = self.data.scope.borrow_mut();
+        scope.get_or_insert_with(Scope::new).clone()
     }
 
     /// Registers a reactive side effect that is canceled when this element
     /// unmounts.
     ///
     /// The effect runs immediately and reruns when tracked signal reads change,
-    /// just like [`effect`]. The returned handle can still be used to cancel the
-    /// effect earlier.
+    /// just like [`effect`](fn@crate::effect). The returned handle can still be
+    /// used to cancel the effect earlier.
     #[track_caller]
     pub fn scoped_effect(&self, f: impl Fn() + 'static) -> EffectHandle {
-        let handle = effect(f);
-        if !handle.is_cancelled() {
-            self.on_scoped_effect_cleanup({
-                let handle = handle.clone();
-                move || handle.cancel()
-            });
-        }
-        handle
+        self.scope().effect(f)
     }
 
     /// Registers a callback to run when this element's placement changes.
@@ -353,28 +400,77 @@ impl Element {
     }
 
     /// Looks up a typed context value from this element.
+    ///
+    /// The lookup walks from this element up its ancestors and returns the
+    /// value of the nearest provider of `T`. Reading the value is tracked, so an
+    /// effect or computed value that calls this reruns when the provider
+    /// changes the value.
     pub fn context<T: 'static>(&self) -> Option<Rc<T>> {
-        self.context_any::<T>()
+        self.context_any(ContextId::Type(TypeId::of::<T>()))
             .map(|ctx| Rc::downcast::<T>(ctx).unwrap())
     }
 
-    pub(crate) fn provide_context<T: 'static>(&self, context: impl Into<Rc<T>>) {
-        let mut contexts = self.data.contexts.borrow_mut();
-        contexts.insert(TypeId::of::<T>(), context.into());
+    /// Provides a context value of type `T` to this element and its
+    /// descendants.
+    ///
+    /// Providing again updates the value for every descendant that already
+    /// read it.
+    pub fn provide_context<T: 'static>(&self, context: impl Into<Rc<T>>) {
+        self.provide_context_any(ContextId::Type(TypeId::of::<T>()), context.into());
     }
 
-    pub(crate) fn contexts(&self) -> HashMap<TypeId, Rc<dyn Any>> {
-        self.data.contexts.borrow().clone()
+    /// Looks up the value provided for `key` from this element.
+    ///
+    /// This behaves like [`Element::context`], but finds the nearest provider
+    /// of `key` instead of the nearest provider of the type.
+    pub fn keyed_context<T: 'static>(&self, key: &ContextKey<T>) -> Option<Rc<T>> {
+        self.context_any(key.context_id())
+            .map(|ctx| Rc::downcast::<T>(ctx).unwrap())
     }
 
-    pub(crate) fn extend_contexts(&self, contexts: HashMap<TypeId, Rc<dyn Any>>) {
-        let mut borrowed_contexts = self.data.contexts.borrow_mut();
-        borrowed_contexts.extend(contexts);
+    /// Provides a context value for `key` to this element and its
+    /// descendants.
+    ///
+    /// Providing again updates the value for every descendant that already
+    /// read it.
+    pub fn provide_keyed_context<T: 'static>(
+        &self,
+        key: &ContextKey<T>,
+        context: impl Into<Rc<T>>,
+    ) {
+        self.provide_context_any(key.context_id(), context.into());
+    }
+
+    fn provide_context_any(&self, id: ContextId, context: Rc<dyn Any>) {
+        let slot = self.data.contexts.borrow().get(&id).cloned();
+        match slot {
+            Some(slot) => {
+                if !untrack(|| Rc::ptr_eq(&slot.borrow(), &context)) {
+                    slot.set_unchecked(context);
+                }
+            }
+            None => {
+                let mut contexts = self.data.contexts.borrow_mut();
+                contexts.insert(id, create_state(context));
+            }
+        }
     }
 
-    fn context_any<T: 'static>(&self) -> Option<Rc<dyn Any>> {
-        let contexts = self.data.contexts.borrow();
-        contexts.get(&TypeId::of::<T>()).cloned()
+    fn context_any(&self, id: ContextId) -> Option<Rc<dyn Any>> {
+        let mut element = Some(self.clone());
+        while let Some(current) = element {
+            let slot = current.data.contexts.borrow().get(&id).cloned();
+            if let Some(slot) = slot {
+                return Some(slot.get());
+            }
+            element = current
+                .data
+                .context_parent
+                .borrow()
+                .as_ref()
+                .and_then(WeakElement::upgrade);
+        }
+        None
     }
 
     fn notify_after_mount(&self) {
@@ -399,6 +495,45 @@ impl Element {
         children.len() != previous_len
     }
 
+    /// Moves this element out of its parent without unmounting it.
+    ///
+    /// Its host nodes are placed without a parent, which removes them from the
+    /// host tree until the element is attached again. Contexts are still
+    /// resolved through the former parent.
+    pub(crate) fn detach(&self) {
+        let Some(parent) = self.parent() else {
+            return;
+        };
+        parent.remove_child(self);
+        self.data.parent.take();
+        self.notify_place(true);
+        parent.notify_last_handle_change();
+    }
+
+    /// Attaches a detached, still mounted element as the last child of
+    /// `parent` and places its host nodes again.
+    pub(crate) fn attach(&self, parent: &Element) {
+        parent.add_child(self.clone());
+        self.data.parent.replace(Some(parent.downgrade()));
+        self.data.context_parent.replace(Some(parent.downgrade()));
+        self.notify_place(true);
+        parent.notify_last_handle_change();
+    }
+
+    fn is_portal(&self) -> bool {
+        self.data.portal_target.borrow().is_some()
+    }
+
+    /// Places this element's descendants under `target` and moves any that
+    /// are already mounted there.
+    pub(crate) fn set_portal_target(&self, target: Shared<dyn Any>) {
+        self.data.portal_target.replace(Some(target));
+        let children = self.data.children.borrow().clone();
+        for child in children {
+            child.notify_place(true);
+        }
+    }
+
     pub fn is_in_list(&self) -> bool {
         self.data.in_list.get()
     }
@@ -484,47 +619,121 @@ impl WeakElement {
     }
 }
 
+/// A handle to a tree mounted with [`mount_root`].
+///
+/// Any number of roots can be mounted on a thread at once, and each one can be
+/// unmounted independently. Dropping a `Root` does not unmount its tree.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Root {
+    element: Element,
+}
+
+impl Root {
+    /// Returns the root element.
+    pub fn element(&self) -> &Element {
+        &self.element
+    }
+
+    /// Returns whether this root is still mounted.
+    pub fn is_mounted(&self) -> bool {
+        MOUNTED_ROOTS.with_borrow(|roots| roots.contains(&self.element))
+    }
+
+    /// Unmounts this root's tree. Does nothing if it is no longer mounted.
+    pub fn unmount(&self) {
+        if self.is_mounted() {
+            self.element.unmount();
+        }
+    }
+}
+
 /// Mounts an element as the root of a tree.
-pub fn mount_root(element: &Element) {
-    MOUNTED_ROOT.with(|root| root.replace(Some(element.clone())));
+///
+/// Previously mounted roots stay mounted. The returned [`Root`] unmounts this
+/// tree alone.
+///
+/// # Panics
+///
+/// Panics if `element` is already mounted as a root.
+pub fn mount_root(element: &Element) -> Root {
+    MOUNTED_ROOTS.with_borrow_mut(|roots| {
+        assert!(
+            !roots.contains(element),
+            "the element is already mounted as a root"
+        );
+        roots.push(element.clone());
+    });
+    LAST_ROOT.with_borrow_mut(|root| root.replace(element.clone()));
     element.on_unmount({
         let element = element.downgrade();
         move || {
-            MOUNTED_ROOT.with(|root| {
-                let mounted_element = root.borrow().as_ref().map(Element::downgrade);
-                if mounted_element == Some(element.clone()) {
+            MOUNTED_ROOTS.with_borrow_mut(|roots| {
+                roots.retain(|root| root.downgrade() != element);
+            });
+            LAST_ROOT.with_borrow_mut(|root| {
+                if root.as_ref().map(Element::downgrade) == Some(element.clone()) {
                     root.take();
                 }
             });
         }
     });
     element.mount(None);
+
+    Root {
+        element: element.clone(),
+    }
 }
 
-/// Unmounts the currently mounted root.
+/// Unmounts the most recently mounted root.
 ///
-/// Returns an error if no root is currently mounted.
+/// Other roots stay mounted; use [`Root::unmount`] to unmount them. Returns an
+/// error if no root is mounted, or if the most recently mounted root has
+/// already been unmounted while older roots are still mounted.
 pub fn unmount_root() -> Result<(), &'static str> {
-    let root = MOUNTED_ROOT.with(|root| root.take());
-    let root = root.ok_or("no root has been mounted")?;
+    let Some(root) = LAST_ROOT.with_borrow_mut(|root| root.take()) else {
+        return Err(if MOUNTED_ROOTS.with_borrow(Vec::is_empty) {
+            "no root is mounted"
+        } else {
+            "the most recently mounted root has already been unmounted"
+        });
+    };
     root.unmount();
     Ok(())
 }
 
 /// Creates an element for component `C` with `props`.
 pub fn create_element<C: Component>(props: C::Props) -> Element {
+    create_element_with_id(component_id::<C>(), Box::new(props))
+}
+
+/// Creates an element for the component identified by `component_id`.
+///
+/// This is the type-erased form of [`create_element`], for components that
+/// are only known at runtime.
+///
+/// # Panics
+///
+/// Panics when `props` is not the props type of the component.
+pub fn create_element_with_id(component_id: ComponentID, props: Box<dyn Props>) -> Element {
+    assert!(
+        props.as_any().type_id() == component_id.props_type_id,
+        "props passed to `create_element_with_id` do not belong to component `{}`",
+        component_id.name
+    );
     Element {
         data: Rc::new(ElementData {
-            component_id: component_id::<C>(),
-            props: Box::new(props),
+            component_id,
+            props,
             contexts: RefCell::new(HashMap::new()),
             handle: RefCell::new(None),
+            portal_target: RefCell::new(None),
             parent: RefCell::new(None),
+            context_parent: RefCell::new(None),
             children: RefCell::new(Vec::new()),
             in_list: Cell::new(false),
             last_handle_snapshot: RefCell::new(None),
             on_last_handle_change_callbacks: RefCell::new(HashSet::new()),
-            scoped_effect_cleanup_callbacks: RefCell::new(HashSet::new()),
+            scope: RefCell::new(None),
             on_unmount_callbacks: RefCell::new(HashSet::new()),
             after_mount_callbacks: RefCell::new(HashSet::new()),
             on_place_callbacks: RefCell::new(HashSet::new()),
@@ -535,18 +744,17 @@ pub fn create_element<C: Component>(props: C::Props) -> Element {
 /// Registers a reactive side effect scoped to the current component element.
 ///
 /// The effect runs immediately and reruns when tracked signal reads change,
-/// just like [`effect`], and is automatically canceled when the current element
-/// unmounts. This function must be called while a component function is
-/// executing. Use [`Element::scoped_effect`] when the element is available
-/// explicitly.
+/// just like [`effect`](fn@crate::effect), and is automatically canceled when
+/// the current element unmounts. This function must be called while a
+/// component function is executing. Use [`Element::scoped_effect`] when the
+/// element is available explicitly.
 ///
 /// # Panics
 ///
 /// Panics when called outside a component function.
 #[track_caller]
 pub fn scoped_effect(f: impl Fn() + 'static) -> EffectHandle {
-    let element = CURRENT_ELEMENT.with(|current| current.borrow().clone());
-    element
+    current_element()
         .expect("scoped_effect must be called inside a component function")
         .scoped_effect(f)
 }
@@ -556,7 +764,8 @@ pub fn scoped_effect(f: impl Fn() + 'static) -> EffectHandle {
 pub struct Placement {
     /// Handle of the preceding host node, if any.
     pub pred: Option<Shared<dyn Any>>,
-    /// Handle of the nearest parent host node, if any.
+    /// Handle of the nearest parent host node, or the target of the nearest
+    /// enclosing portal, if any.
     pub parent: Option<Shared<dyn Any>>,
     /// Index of the element in the nearest list, if any.
     pub index: Option<usize>,
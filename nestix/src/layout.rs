@@ -2,7 +2,7 @@ use std::ops::Index;
 
 use crate::Element;
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 enum LayoutInner {
     Empty,
     Element(Element),
@@ -13,8 +13,8 @@ enum LayoutInner {
 ///
 /// Layouts are used for children and component return values. They can be
 /// created from `()`, a single [`Element`], an `Option<Element>`, or a vector of
-/// elements.
-#[derive(Debug, Clone)]
+/// elements. Layouts are equal when they contain the same elements.
+#[derive(Debug, Clone, PartialEq)]
 pub struct Layout(LayoutInner);
 
 impl Layout {
@@ -27,6 +27,11 @@ impl Layout {
         }
     }
 
+    /// Returns whether this layout contains no elements.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
     /// Returns the element at `index`, or `None` when it is out of bounds.
     pub fn get(&self, index: usize) -> Option<&Element> {
         match &self.0 {
@@ -123,7 +128,7 @@ impl<'a> IntoIterator for &'a Layout {
         match &self.0 {
             LayoutInner::Empty => Iter::Empty,
             LayoutInner::Element(element) => Iter::Element(std::iter::once(element)),
-            LayoutInner::ElementList(elements) => Iter::ElementList(elements.into_iter()),
+            LayoutInner::ElementList(elements) => Iter::ElementList(elements.iter()),
         }
     }
 }
@@ -9,14 +9,20 @@ pub mod components;
 /// Element creation, mounting, lifecycle, and placement APIs.
 pub mod element;
 
+mod context;
 mod layout;
 mod prop;
+mod renderer;
+mod ssr;
 mod utils;
 
 pub use components::*;
+pub use context::*;
 pub use element::*;
 pub use layout::*;
 pub use prop::*;
+pub use renderer::*;
+pub use ssr::*;
 
 pub use nestix_macros::*;
 pub use nestix_signal::*;
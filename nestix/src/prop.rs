@@ -158,6 +158,7 @@ pub struct PlainTag<T>(PhantomData<T>);
 impl<T> PlainTag<T> {
     /// Converts a plain value into a [`PropValue`].
     #[inline]
+    #[allow(clippy::new_ret_no_self)]
     pub fn new(self, value: impl Into<T>) -> PropValue<T> {
         PropValue::from_plain(value.into())
     }
@@ -181,6 +182,7 @@ pub struct SignalTag<T>(PhantomData<T>);
 impl<T> SignalTag<T> {
     /// Converts a signal into a [`PropValue`].
     #[inline]
+    #[allow(clippy::new_ret_no_self)]
     pub fn new<U: Into<T>, S: Signal<Output = U> + 'static>(self, value: S) -> PropValue<T> {
         PropValue::from_signal(value)
     }
@@ -209,6 +211,7 @@ pub struct PropValueTag<T>(PhantomData<T>);
 impl<T> PropValueTag<T> {
     /// Returns an existing [`PropValue`] unchanged.
     #[inline]
+    #[allow(clippy::new_ret_no_self)]
     pub fn new(self, value: PropValue<T>) -> PropValue<T> {
         value
     }
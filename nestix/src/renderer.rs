@@ -0,0 +1,135 @@
+use std::{cell::Cell, rc::Rc};
+
+use crate::{Element, Placement, Root, mount_root};
+
+/// A rendering backend that creates and arranges host nodes.
+///
+/// A renderer is registered once per root with [`mount_root_with_renderer`].
+/// Host components then call [`Element::create_host_node`] to describe their
+/// node, and the runtime inserts, moves, and removes it as the element is
+/// placed and unmounted.
+///
+/// Nodes are always placed relative to their previous host sibling, which is
+/// known when an element is placed, rather than to their next one, which is
+/// not. Inserting after that sibling, or at the start of the parent when there
+/// is none, reaches every position including the end, so the trait has no
+/// `append` operation: appending is an insertion after the last child.
+pub trait Renderer: 'static {
+    /// The host node type. Host elements provide nodes of this type as their
+    /// handle.
+    type Node: Clone + 'static;
+    /// A description from which the renderer creates a node.
+    type NodeDesc;
+
+    /// Creates a detached node from `desc`.
+    fn create_node(&self, desc: Self::NodeDesc) -> Self::Node;
+
+    /// Inserts `node` into `parent` directly after the existing child `pred`.
+    fn insert_after(&self, parent: &Self::Node, pred: &Self::Node, node: &Self::Node);
+
+    /// Inserts `node` as the first child of `parent`.
+    ///
+    /// Used when `node` has no previous host sibling in `parent`.
+    fn prepend(&self, parent: &Self::Node, node: &Self::Node);
+
+    /// Removes `node` from its parent.
+    fn remove(&self, node: &Self::Node);
+
+    /// Moves an already inserted `node` after `pred`, or to the start of
+    /// `parent` when `pred` is `None`.
+    ///
+    /// The default implementation removes the node and inserts it again.
+    fn move_node(&self, parent: &Self::Node, pred: Option<&Self::Node>, node: &Self::Node) {
+        self.remove(node);
+        match pred {
+            Some(pred) => self.insert_after(parent, pred, node),
+            None => self.prepend(parent, node),
+        }
+    }
+}
+
+struct RendererContext<R>(R);
+
+/// Registers `renderer` on `element` and mounts it as the root of a tree.
+///
+/// Every host component in the tree that calls
+/// [`Element::create_host_node::<R>`](Element::create_host_node) uses this
+/// renderer.
+pub fn mount_root_with_renderer<R: Renderer>(element: &Element, renderer: R) -> Root {
+    element.provide_context::<RendererContext<R>>(RendererContext(renderer));
+    mount_root(element)
+}
+
+impl Element {
+    /// Creates a host node with the root's renderer and provides it as this
+    /// element's handle.
+    ///
+    /// The node is inserted into its nearest host parent whenever this element
+    /// is placed, and removed when this element unmounts. Placements without a
+    /// host parent of type `R::Node` detach the node.
+    ///
+    /// # Panics
+    ///
+    /// Panics when no renderer of type `R` was registered for this element's
+    /// root with [`mount_root_with_renderer`].
+    pub fn create_host_node<R: Renderer>(&self, desc: R::NodeDesc) -> R::Node {
+        let renderer = self.context::<RendererContext<R>>().unwrap_or_else(|| {
+            panic!(
+                "no renderer of type `{}` is registered for this root",
+                std::any::type_name::<R>()
+            )
+        });
+        let node = renderer.0.create_node(desc);
+        let inserted = Rc::new(Cell::new(false));
+
+        self.on_place({
+            let renderer = renderer.clone();
+            let node = node.clone();
+            let inserted = inserted.clone();
+            move |placement| place_host_node(&renderer.0, &node, &inserted, placement)
+        });
+
+        self.on_unmount({
+            let node = node.clone();
+            move || {
+                if inserted.replace(false) {
+                    renderer.0.remove(&node);
+                }
+            }
+        });
+
+        self.provide_handle(node.clone());
+        node
+    }
+}
+
+fn place_host_node<R: Renderer>(
+    renderer: &R,
+    node: &R::Node,
+    inserted: &Cell<bool>,
+    placement: &Placement,
+) {
+    let parent = placement
+        .parent
+        .as_ref()
+        .and_then(|parent| parent.downcast_ref::<R::Node>());
+    let Some(parent) = parent else {
+        if inserted.replace(false) {
+            renderer.remove(node);
+        }
+        return;
+    };
+
+    let pred = placement
+        .pred
+        .as_ref()
+        .and_then(|pred| pred.downcast_ref::<R::Node>());
+
+    if inserted.replace(true) {
+        renderer.move_node(parent, pred, node);
+    } else if let Some(pred) = pred {
+        renderer.insert_after(parent, pred, node);
+    } else {
+        renderer.prepend(parent, node);
+    }
+}
@@ -0,0 +1,158 @@
+use std::fmt::Write;
+
+use nestix_macros::{component, layout, props};
+
+use crate::{Element, Fragment, FragmentProps, Layout, PropValue, create_element, mount_root};
+
+/// Elements that never have children or a closing tag in HTML.
+const VOID_TAGS: &[&str] = &[
+    "area", "base", "br", "col", "embed", "hr", "img", "input", "link", "meta", "source", "track",
+    "wbr",
+];
+
+/// A host handle provided by [`HtmlElement`] and [`HtmlText`].
+///
+/// [`render_to_string`] serializes every element in the tree whose handle is
+/// an `HtmlNode`. Elements without one are transparent.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum HtmlNode {
+    /// An HTML element with its tag name and attributes.
+    Element {
+        /// Tag name, such as `"div"`.
+        tag: String,
+        /// Attributes in the order they are written.
+        attributes: Vec<(String, String)>,
+    },
+    /// A text node.
+    Text(String),
+}
+
+/// Props for [`HtmlElement`].
+#[props(debug)]
+#[derive(Debug)]
+pub struct HtmlElementProps {
+    #[props(start)]
+    tag: String,
+    #[props(default)]
+    attributes: Vec<(String, String)>,
+    #[props(default)]
+    children: Layout,
+}
+
+/// Renders an HTML element for [`render_to_string`].
+///
+/// Props are read once when the element mounts.
+///
+/// # Panics
+///
+/// Panics when the tag or an attribute name is empty or contains whitespace,
+/// a control character, or one of `<`, `>`, `"`, `'`, `/` and `=`, since such
+/// a name would change the structure of the rendered markup.
+#[component]
+pub fn HtmlElement(props: &HtmlElementProps, element: &Element) -> Element {
+    let tag = props.tag.get();
+    assert!(is_valid_name(&tag), "invalid HTML tag name `{tag}`");
+    let attributes = props.attributes.get();
+    for (name, _) in &attributes {
+        assert!(is_valid_name(name), "invalid HTML attribute name `{name}`");
+    }
+    element.provide_handle(HtmlNode::Element { tag, attributes });
+
+    layout! {
+        Fragment {
+            $(props.children.clone())
+        }
+    }
+}
+
+/// Props for [`HtmlText`].
+#[props(debug)]
+#[derive(Debug)]
+pub struct HtmlTextProps {
+    #[props(start)]
+    text: String,
+}
+
+/// Renders a text node for [`render_to_string`].
+///
+/// The text is read once when the element mounts.
+#[component]
+pub fn HtmlText(props: &HtmlTextProps, element: &Element) {
+    element.provide_handle(HtmlNode::Text(props.text.get()));
+}
+
+/// Renders `layout` to an HTML string.
+///
+/// The layout is mounted as its own root, serialized from the
+/// [`HtmlNode`] handles provided by [`HtmlElement`] and [`HtmlText`], and then
+/// unmounted. Signals are therefore read once, and scoped effects created
+/// while mounting are canceled before this function returns.
+///
+/// Text and attribute values are escaped, and [`HtmlElement`] rejects tag and
+/// attribute names that could inject markup. Void elements such as `br` and
+/// `input` are written without a closing tag.
+pub fn render_to_string(layout: impl Into<Layout>) -> String {
+    let element = create_element::<Fragment>(FragmentProps {
+        children: PropValue::from_plain(layout.into()),
+    });
+    let root = mount_root(&element);
+
+    let mut output = String::new();
+    write_element(root.element(), &mut output);
+
+    root.unmount();
+    output
+}
+
+fn write_element(element: &Element, output: &mut String) {
+    let handle = element.handle();
+    let node = handle
+        .as_ref()
+        .and_then(|handle| handle.downcast_ref::<HtmlNode>());
+
+    match node {
+        Some(HtmlNode::Text(text)) => write_escaped(output, text, false),
+        Some(HtmlNode::Element { tag, attributes }) => {
+            write!(output, "<{tag}").unwrap();
+            for (name, value) in attributes {
+                write!(output, " {name}=\"").unwrap();
+                write_escaped(output, value, true);
+                output.push('"');
+            }
+            output.push('>');
+
+            if VOID_TAGS.contains(&tag.as_str()) {
+                return;
+            }
+
+            write_children(element, output);
+            write!(output, "</{tag}>").unwrap();
+        }
+        None => write_children(element, output),
+    }
+}
+
+fn write_children(element: &Element, output: &mut String) {
+    for child in element.children() {
+        write_element(&child, output);
+    }
+}
+
+fn is_valid_name(name: &str) -> bool {
+    !name.is_empty()
+        && !name.chars().any(|c| {
+            c.is_whitespace() || c.is_control() || matches!(c, '<' | '>' | '"' | '\'' | '/' | '=')
+        })
+}
+
+fn write_escaped(output: &mut String, value: &str, in_attribute: bool) {
+    for c in value.chars() {
+        match c {
+            '&' => output.push_str("&amp;"),
+            '<' => output.push_str("&lt;"),
+            '>' => output.push_str("&gt;"),
+            '"' if in_attribute => output.push_str("&quot;"),
+            c => output.push(c),
+        }
+    }
+}
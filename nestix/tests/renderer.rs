@@ -0,0 +1,144 @@
+use std::{cell::RefCell, rc::Rc};
+
+use nestix::{
+    Element, Fragment, Layout, Renderer, component, create_state, layout, mount_root,
+    mount_root_with_renderer, props, unmount_root,
+};
+
+#[derive(Clone, Default)]
+struct RecordingRenderer {
+    operations: Rc<RefCell<Vec<String>>>,
+}
+
+impl RecordingRenderer {
+    fn take_operations(&self) -> Vec<String> {
+        self.operations.take()
+    }
+
+    fn record(&self, operation: String) {
+        self.operations.borrow_mut().push(operation);
+    }
+}
+
+impl Renderer for RecordingRenderer {
+    type Node = String;
+    type NodeDesc = &'static str;
+
+    fn create_node(&self, desc: Self::NodeDesc) -> Self::Node {
+        desc.to_string()
+    }
+
+    fn insert_after(&self, parent: &Self::Node, pred: &Self::Node, node: &Self::Node) {
+        self.record(format!("insert {node} into {parent} after {pred}"));
+    }
+
+    fn prepend(&self, parent: &Self::Node, node: &Self::Node) {
+        self.record(format!("prepend {node} into {parent}"));
+    }
+
+    fn remove(&self, node: &Self::Node) {
+        self.record(format!("remove {node}"));
+    }
+
+    fn move_node(&self, parent: &Self::Node, pred: Option<&Self::Node>, node: &Self::Node) {
+        match pred {
+            Some(pred) => self.record(format!("move {node} in {parent} after {pred}")),
+            None => self.record(format!("move {node} to start of {parent}")),
+        }
+    }
+}
+
+#[props]
+struct HostProps {
+    #[props(start)]
+    name: &'static str,
+    #[props(default)]
+    children: Layout,
+}
+
+#[component]
+fn Host(props: &HostProps, element: &Element) -> Element {
+    element.create_host_node::<RecordingRenderer>(props.name.get());
+
+    layout! {
+        Fragment {
+            $(props.children.clone())
+        }
+    }
+}
+
+#[test]
+fn host_nodes_are_inserted_in_layout_order() {
+    let renderer = RecordingRenderer::default();
+    let app = layout! {
+        Host("root") {
+            Host("a")
+            Host("b") {
+                Host("c")
+            }
+        }
+    };
+
+    mount_root_with_renderer(&app, renderer.clone());
+
+    assert_eq!(
+        renderer.take_operations(),
+        vec![
+            "prepend a into root",
+            "prepend c into b",
+            "insert b into root after a",
+        ]
+    );
+
+    unmount_root().unwrap();
+
+    let operations = renderer.take_operations();
+    assert!(operations.contains(&String::from("remove a")));
+    assert!(operations.contains(&String::from("remove b")));
+}
+
+#[test]
+fn conditional_host_nodes_are_inserted_and_removed() {
+    let renderer = RecordingRenderer::default();
+    let show = create_state(false);
+    let show_in_layout = show.clone();
+    let app = layout! {
+        Host("root") {
+            Host("first")
+            if show_in_layout.get() {
+                Host("middle")
+            }
+            Host("last")
+        }
+    };
+
+    mount_root_with_renderer(&app, renderer.clone());
+    renderer.take_operations();
+
+    show.set(true);
+    assert_eq!(
+        renderer.take_operations(),
+        vec![
+            "insert middle into root after first",
+            "move last in root after middle",
+        ]
+    );
+
+    show.set(false);
+    assert_eq!(
+        renderer.take_operations(),
+        vec!["remove middle", "move last in root after first"]
+    );
+
+    unmount_root().unwrap();
+}
+
+#[test]
+#[should_panic(expected = "no renderer of type")]
+fn creating_host_node_without_renderer_panics() {
+    let app = layout! {
+        Host("root")
+    };
+
+    mount_root(&app);
+}
@@ -5,7 +5,8 @@ use std::{
 
 use nestix::{
     Component, ComponentOutput, Element, Fragment, FragmentProps, Layout, Placement, PropValue,
-    Props, create_element, create_state, mount_root, unmount_root,
+    Props, State, create_element, create_root, create_state, effect, mount_root, on_cleanup,
+    unmount_root,
 };
 
 struct Empty;
@@ -82,7 +83,7 @@ fn unmount_root_unmounts_the_mounted_root() {
 
     assert_eq!(unmount_root(), Ok(()));
     assert_eq!(unmounts.get(), 1);
-    assert!(unmount_root().is_err());
+    assert_eq!(unmount_root(), Err("no root is mounted"));
 }
 
 #[test]
@@ -91,9 +92,89 @@ fn unmount_root_errors_after_direct_root_unmount() {
     mount_root(&root);
     root.unmount();
 
+    assert_eq!(unmount_root(), Err("no root is mounted"));
+}
+
+#[test]
+fn unmount_root_errors_when_the_most_recent_root_was_unmounted_directly() {
+    let first = mount_root(&create_element::<Empty>(()));
+    mount_root(&create_element::<Empty>(())).unmount();
+
+    assert_eq!(
+        unmount_root(),
+        Err("the most recently mounted root has already been unmounted")
+    );
+    assert!(first.is_mounted());
+
+    first.unmount();
+}
+
+#[test]
+fn mounted_roots_unmount_independently() {
+    let unmounts = Rc::new(RefCell::new(Vec::new()));
+    let first = create_element::<Empty>(());
+    let second = create_element::<Empty>(());
+    for (name, element) in [("first", &first), ("second", &second)] {
+        element.on_unmount({
+            let unmounts = unmounts.clone();
+            move || unmounts.borrow_mut().push(name)
+        });
+    }
+
+    let first_root = mount_root(&first);
+    let second_root = mount_root(&second);
+
+    assert_eq!(first_root.element(), &first);
+    assert!(first_root.is_mounted());
+    assert!(second_root.is_mounted());
+
+    first_root.unmount();
+    first_root.unmount();
+
+    assert!(!first_root.is_mounted());
+    assert!(second_root.is_mounted());
+    assert_eq!(*unmounts.borrow(), vec!["first"]);
+
+    second_root.unmount();
+
+    assert_eq!(*unmounts.borrow(), vec!["first", "second"]);
     assert!(unmount_root().is_err());
 }
 
+#[test]
+fn unmount_root_unmounts_only_the_most_recent_root() {
+    let first = mount_root(&create_element::<Empty>(()));
+    let second = mount_root(&create_element::<Empty>(()));
+
+    assert_eq!(unmount_root(), Ok(()));
+    assert!(first.is_mounted());
+    assert!(!second.is_mounted());
+    assert_eq!(
+        unmount_root(),
+        Err("the most recently mounted root has already been unmounted")
+    );
+
+    first.unmount();
+}
+
+#[test]
+#[should_panic(expected = "already mounted as a root")]
+fn mounting_a_mounted_root_again_panics() {
+    let element = create_element::<Empty>(());
+    mount_root(&element);
+    mount_root(&element);
+}
+
+#[test]
+fn unmounted_roots_can_be_mounted_again() {
+    let element = create_element::<Empty>(());
+    mount_root(&element).unmount();
+
+    let root = mount_root(&element);
+    assert!(root.is_mounted());
+    assert_eq!(unmount_root(), Ok(()));
+}
+
 #[test]
 fn layout_conversions_expose_expected_elements() {
     let first = create_element::<Empty>(());
@@ -295,6 +376,99 @@ fn scoped_effect_is_cancelled_when_element_unmounts() {
     assert_eq!(runs.get(), 2);
 }
 
+struct PlainEffect;
+
+struct PlainEffectProps {
+    value: State<i32>,
+    runs: Rc<Cell<usize>>,
+}
+
+impl Props for PlainEffectProps {}
+
+impl Component for PlainEffect {
+    type Props = PlainEffectProps;
+
+    fn on_mount(element: &Element) {
+        let props = element.props().downcast_ref::<PlainEffectProps>().unwrap();
+        let value = props.value.clone();
+        let runs = props.runs.clone();
+        effect(move || {
+            value.get();
+            runs.set(runs.get() + 1);
+        });
+    }
+}
+
+#[test]
+fn effects_created_while_mounting_are_owned_by_the_element_scope() {
+    let value = create_state(1);
+    let runs = Rc::new(Cell::new(0));
+    let root = create_element::<PlainEffect>(PlainEffectProps {
+        value: value.clone(),
+        runs: runs.clone(),
+    });
+
+    mount_root(&root);
+    value.set(2);
+    assert_eq!(runs.get(), 2);
+
+    root.unmount();
+    value.set(3);
+
+    assert!(root.scope().is_disposed());
+    assert_eq!(runs.get(), 2);
+}
+
+struct CleanupOnUnmount;
+
+impl Component for CleanupOnUnmount {
+    type Props = CountMountsProps;
+
+    fn on_mount(element: &Element) {
+        let count = element
+            .props()
+            .downcast_ref::<CountMountsProps>()
+            .unwrap()
+            .count
+            .clone();
+        on_cleanup(move || count.set(count.get() + 1));
+    }
+}
+
+#[test]
+fn cleanups_registered_while_mounting_run_when_the_element_unmounts() {
+    let count = Rc::new(Cell::new(0));
+    let root = create_element::<CleanupOnUnmount>(CountMountsProps {
+        count: count.clone(),
+    });
+
+    mount_root(&root);
+    assert_eq!(count.get(), 0);
+
+    root.unmount();
+    assert_eq!(count.get(), 1);
+}
+
+#[test]
+fn disposing_a_root_scope_cancels_effects_of_elements_mounted_inside_it() {
+    let value = create_state(1);
+    let runs = Rc::new(Cell::new(0));
+
+    let scope = create_root(|scope| {
+        let root = create_element::<PlainEffect>(PlainEffectProps {
+            value: value.clone(),
+            runs: runs.clone(),
+        });
+        root.mount(None);
+        scope
+    });
+
+    scope.dispose();
+    value.set(2);
+
+    assert_eq!(runs.get(), 1);
+}
+
 #[test]
 #[should_panic(expected = "scoped_effect must be called inside a component function")]
 fn scoped_effect_requires_a_current_component_element() {
@@ -0,0 +1,142 @@
+use std::{cell::Cell, rc::Rc};
+
+use nestix::{
+    Element, HtmlElement, HtmlText, component, create_state, layout, props, render_to_string,
+    scoped_effect,
+};
+
+#[test]
+fn renders_nested_elements_in_layout_order() {
+    let html = render_to_string(layout! {
+        HtmlElement("ul", .attributes = vec![("id".to_string(), "items".to_string())]) {
+            HtmlElement("li") {
+                HtmlText("first")
+            }
+            HtmlElement("li") {
+                HtmlText("second")
+            }
+        }
+    });
+
+    assert_eq!(html, "<ul id=\"items\"><li>first</li><li>second</li></ul>");
+}
+
+#[test]
+fn escapes_text_and_attribute_values() {
+    let html = render_to_string(layout! {
+        HtmlElement("a", .attributes = vec![("title".to_string(), "\"Tom\" & <Jerry>".to_string())]) {
+            HtmlText("1 < 2 && \"quoted\"")
+        }
+    });
+
+    assert_eq!(
+        html,
+        "<a title=\"&quot;Tom&quot; &amp; &lt;Jerry&gt;\">1 &lt; 2 &amp;&amp; \"quoted\"</a>"
+    );
+}
+
+#[test]
+#[should_panic(expected = "invalid HTML tag name")]
+fn tag_names_that_inject_markup_are_rejected() {
+    render_to_string(layout! {
+        HtmlElement("img src=x onerror=alert(1)")
+    });
+}
+
+#[test]
+#[should_panic(expected = "invalid HTML attribute name")]
+fn attribute_names_that_inject_markup_are_rejected() {
+    render_to_string(layout! {
+        HtmlElement("a", .attributes = vec![("href=\"x\"><script>".to_string(), String::new())])
+    });
+}
+
+#[test]
+fn void_elements_have_no_closing_tag() {
+    let html = render_to_string(layout! {
+        HtmlElement("p") {
+            HtmlText("line")
+            HtmlElement("br")
+            HtmlElement("input", .attributes = vec![("value".to_string(), "x".to_string())])
+        }
+    });
+
+    assert_eq!(html, "<p>line<br><input value=\"x\"></p>");
+}
+
+#[props]
+struct GreetingProps {
+    #[props(start)]
+    name: String,
+}
+
+#[component]
+fn Greeting(props: &GreetingProps) -> Element {
+    let name = props.name.clone();
+
+    layout! {
+        HtmlElement("h1") {
+            HtmlText(format!("Hello, {}!", name.get()))
+        }
+    }
+}
+
+#[test]
+fn renders_components_conditionals_and_lists_from_signals() {
+    let logged_in = create_state(true);
+    let items = create_state(vec![1, 2, 3]);
+    let logged_in_in_layout = logged_in.clone();
+
+    let html = render_to_string(layout! {
+        HtmlElement("main") {
+            if logged_in_in_layout.get() {
+                Greeting("Ada")
+            } else {
+                HtmlText("Please log in")
+            }
+            for item in items.clone() {
+                HtmlElement("span") {
+                    HtmlText(item.get().to_string())
+                }
+            }
+        }
+    });
+
+    assert_eq!(
+        html,
+        "<main><h1>Hello, Ada!</h1><span>1</span><span>2</span><span>3</span></main>"
+    );
+}
+
+#[props]
+struct TrackedProps {
+    value: i32,
+    runs: Rc<Cell<usize>>,
+}
+
+#[component]
+fn Tracked(props: &TrackedProps) {
+    let runs = props.runs.get();
+    scoped_effect!(
+        [props.value] || {
+            value.get();
+            runs.set(runs.get() + 1);
+        }
+    );
+}
+
+#[test]
+fn scoped_effects_are_torn_down_after_rendering() {
+    let value = create_state(0);
+    let runs = Rc::new(Cell::new(0));
+    let html = render_to_string(layout! {
+        Tracked(.value = value.clone(), .runs = runs.clone())
+    });
+
+    assert_eq!(html, "");
+    assert_eq!(runs.get(), 1);
+
+    value.set(1);
+
+    assert_eq!(runs.get(), 1);
+}
@@ -0,0 +1,105 @@
+use std::{cell::Cell, rc::Rc};
+
+use nestix::{Store, create_store, effect};
+
+#[derive(Debug, Clone, PartialEq, Store)]
+struct User {
+    name: String,
+    age: u32,
+}
+
+#[derive(Debug, Clone, PartialEq, Store)]
+struct AppState {
+    #[store(nested)]
+    user: User,
+    theme: String,
+}
+
+fn app_state() -> AppState {
+    AppState {
+        user: User {
+            name: "Alice".to_string(),
+            age: 30,
+        },
+        theme: "light".to_string(),
+    }
+}
+
+#[test]
+fn field_writes_only_notify_readers_of_that_field() {
+    let store = create_store(app_state());
+    let name_runs = Rc::new(Cell::new(0));
+    let theme_runs = Rc::new(Cell::new(0));
+
+    effect({
+        let store = store.clone();
+        let name_runs = name_runs.clone();
+        move || {
+            store.user().name().get();
+            name_runs.set(name_runs.get() + 1);
+        }
+    });
+    effect({
+        let store = store.clone();
+        let theme_runs = theme_runs.clone();
+        move || {
+            store.theme().get();
+            theme_runs.set(theme_runs.get() + 1);
+        }
+    });
+
+    store.user().name().set("Bob".to_string());
+    store.user().age().set(31);
+
+    assert_eq!(name_runs.get(), 2);
+    assert_eq!(theme_runs.get(), 1);
+    assert_eq!(store.user().name().get(), "Bob");
+}
+
+#[test]
+fn snapshots_read_and_replace_the_whole_value() {
+    let store = create_store(app_state());
+    let snapshot_runs = Rc::new(Cell::new(0));
+    let age_runs = Rc::new(Cell::new(0));
+
+    effect({
+        let store = store.clone();
+        let snapshot_runs = snapshot_runs.clone();
+        move || {
+            store.get();
+            snapshot_runs.set(snapshot_runs.get() + 1);
+        }
+    });
+    effect({
+        let store = store.clone();
+        let age_runs = age_runs.clone();
+        move || {
+            store.user().age().get();
+            age_runs.set(age_runs.get() + 1);
+        }
+    });
+
+    store.theme().set("dark".to_string());
+    assert_eq!(snapshot_runs.get(), 2);
+
+    store.update(|state| AppState {
+        theme: "light".to_string(),
+        user: User {
+            name: "Carol".to_string(),
+            ..state.user.clone()
+        },
+    });
+
+    assert_eq!(snapshot_runs.get(), 3);
+    assert_eq!(age_runs.get(), 1);
+    assert_eq!(
+        store.get(),
+        AppState {
+            user: User {
+                name: "Carol".to_string(),
+                age: 30,
+            },
+            theme: "light".to_string(),
+        }
+    );
+}